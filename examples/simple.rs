@@ -9,6 +9,7 @@ use Lbl::*;
 pub fn main() -> Result<()> {
     let mut stdy = Stdy::new();
     stdy.reg_bld(&[Alc, Arr], |x| {
+        x.elm(Cnt::Lbl(1)).byt(Cnt::Lbl(4)).spc(Spc::Log);
         x.ins(Len(16), || [0u32; 16]);
         x.ins(Len(32), || [0u32; 32]);
         x.ins(Len(64), || [0u32; 64]);
@@ -25,20 +26,8 @@ pub fn main() -> Result<()> {
         x.ins(Len(131072), || [0u32; 131072]);
     });
     stdy.reg_bld(&[Alc, Vct, Mcr], |x| {
-        x.ins(Len(16), || vec![0u32; 16]);
-        x.ins(Len(32), || vec![0u32; 32]);
-        x.ins(Len(64), || vec![0u32; 64]);
-        x.ins(Len(128), || vec![0u32; 128]);
-        x.ins(Len(256), || vec![0u32; 256]);
-        x.ins(Len(512), || vec![0u32; 512]);
-        x.ins(Len(1024), || vec![0u32; 1024]);
-        x.ins(Len(2048), || vec![0u32; 2048]);
-        x.ins(Len(4096), || vec![0u32; 4096]);
-        x.ins(Len(8192), || vec![0u32; 8192]);
-        x.ins(Len(16384), || vec![0u32; 16384]);
-        x.ins(Len(32768), || vec![0u32; 32768]);
-        x.ins(Len(65536), || vec![0u32; 65536]);
-        x.ins(Len(131072), || vec![0u32; 131072]);
+        x.elm(Cnt::Lbl(1)).byt(Cnt::Lbl(4));
+        x.swp(Len, Swp::Pow2(16, 131072), |n| vec![0u32; n as usize]);
     });
 
     let itr: u16 = 64;
@@ -0,0 +1,75 @@
+use anyhow::{bail, Result};
+use ben::*;
+use std::fmt;
+use Lbl::*;
+
+const LEN: usize = 4096;
+
+// clear && cargo r -q --example typ --profile release
+pub fn main() -> Result<()> {
+    let mut stdy = Stdy::new();
+    stdy.reg_bld(&[Srt, Stb], |x| {
+        x.elm(Cnt::Fix(LEN as u64));
+        ins_typs!(x, Ty, T in [u8, u16, u32, u64, u128], || {
+            let mut v: Vec<T> = (0..LEN).map(|n| (n * 7_919 % 251) as T).collect();
+            v.sort();
+            v
+        });
+    });
+    stdy.reg_bld(&[Srt, Ust], |x| {
+        x.elm(Cnt::Fix(LEN as u64));
+        ins_typs!(x, Ty, T in [u8, u16, u32, u64, u128], || {
+            let mut v: Vec<T> = (0..LEN).map(|n| (n * 7_919 % 251) as T).collect();
+            v.sort_unstable();
+            v
+        });
+    });
+
+    let itr: u16 = 32;
+    let mut qry = QryBld::new();
+    let stb_id = qry.sel(&[Srt, Stb]);
+    let ust_id = qry.sel(&[Srt, Ust]);
+    qry.cmp(stb_id, ust_id);
+
+    stdy.run(qry, itr)?;
+    Ok(())
+}
+
+/// Benchmark labels.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Lbl {
+    Srt,
+    Stb,
+    Ust,
+    Ty(Typ),
+}
+impl fmt::Display for Lbl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Srt => write!(f, "srt"),
+            Stb => write!(f, "stb"),
+            Ust => write!(f, "ust"),
+            Ty(x) => {
+                if f.alternate() {
+                    write!(f, "ty")
+                } else {
+                    write!(f, "ty({})", x)
+                }
+            }
+        }
+    }
+}
+impl EnumStructVal for Lbl {
+    fn val(&self) -> Result<u32> {
+        // Types have no numeric value, which skips crossovers and complexity fits.
+        bail!("label '{}' has no numeric value", self)
+    }
+    fn hdr(&self) -> String {
+        match *self {
+            Ty(x) => x.to_string(),
+            _ => String::new(),
+        }
+    }
+}
+impl Label for Lbl {}
@@ -0,0 +1,133 @@
+//! Provides bootstrap confidence intervals.
+
+use crate::*;
+
+/// Bootstrap resampling settings.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Bts {
+    /// Count of resamples.
+    pub cnt: u32,
+    /// Confidence level in the range `(0, 1)`, e.g. `0.95`.
+    pub cnf: f64,
+    /// Seed for reproducible resampling.
+    pub sed: u64,
+}
+impl Default for Bts {
+    fn default() -> Self {
+        Bts {
+            cnt: 1_000,
+            cnf: 0.95,
+            sed: 0,
+        }
+    }
+}
+impl Bts {
+    /// Returns new bootstrap resampling settings.
+    pub fn new(cnt: u32, cnf: f64, sed: u64) -> Self {
+        Bts { cnt, cnf, sed }
+    }
+
+    /// Returns statistic values of resampled benchmark values.
+    pub fn rsm(&self, vals: &[u64], sta: Sta, rng: &mut Rng) -> Vec<u64> {
+        let mut ret = Vec::with_capacity(self.cnt as usize);
+        if vals.is_empty() {
+            return ret;
+        }
+        let mut buf = vec![0u64; vals.len()];
+        for _ in 0..self.cnt {
+            // Resample with replacement.
+            for v in buf.iter_mut() {
+                *v = vals[rng.below(vals.len() as u64) as usize];
+            }
+            ret.push(sta.cal(&mut buf));
+        }
+        ret
+    }
+
+    /// Returns a confidence interval for a statistic of benchmark values.
+    pub fn ci_sta(&self, smry: &Smry, sta: Sta, rng: &mut Rng) -> Ci {
+        let mut rsms = self.rsm(smry.kep(), sta, rng);
+        rsms.sort_unstable();
+        self.ci(&rsms.iter().map(|x| *x as f64).collect::<Vec<f64>>())
+    }
+
+    /// Returns a confidence interval for the ratio of two statistics.
+    ///
+    /// Each side is resampled with its own statistic.
+    /// The ratio is oriented by the point estimates as max / min,
+    /// and each resampled ratio keeps the same orientation.
+    pub fn ci_rto(&self, a: &Smry, a_sta: Sta, b: &Smry, b_sta: Sta, rng: &mut Rng) -> Ci {
+        let a_rsms = self.rsm(a.kep(), a_sta, rng);
+        let b_rsms = self.rsm(b.kep(), b_sta, rng);
+        let a_is_min = a.sta(a_sta) < b.sta(b_sta);
+        let mut rtos: Vec<f64> = a_rsms
+            .iter()
+            .zip(b_rsms.iter())
+            .map(|(a, b)| {
+                let (min, max) = if a_is_min { (*a, *b) } else { (*b, *a) };
+                max as f64 / (min as f64).max(1.0)
+            })
+            .collect();
+        rtos.sort_unstable_by(|a, b| a.total_cmp(b));
+        self.ci(&rtos)
+    }
+
+    /// Returns a percentile interval from sorted resampled values.
+    fn ci(&self, srt: &[f64]) -> Ci {
+        if srt.is_empty() {
+            return Ci::default();
+        }
+        let tail = (1.0 - self.cnf.clamp(0.0, 1.0)) / 2.0;
+        let qnt = |p: f64| {
+            let pos = p * (srt.len() - 1) as f64;
+            let lo = pos.floor() as usize;
+            let hi = pos.ceil() as usize;
+            srt[lo] + (srt[hi] - srt[lo]) * (pos - lo as f64)
+        };
+        Ci {
+            lo: qnt(tail),
+            hi: qnt(1.0 - tail),
+        }
+    }
+}
+
+/// A confidence interval.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Ci {
+    /// Lower bound.
+    pub lo: f64,
+    /// Upper bound.
+    pub hi: f64,
+}
+
+#[cfg(test)]
+mod tst {
+    use super::*;
+
+    #[test]
+    fn ci_sta_contains_mdn() {
+        let smry = Smry::new((100..200).collect(), false);
+        let ci = Bts::default().ci_sta(&smry, Mdn, &mut Rng::new(7));
+        assert!(ci.lo < ci.hi);
+        assert!(ci.lo <= 150.0 && 150.0 <= ci.hi, "{:?}", ci);
+    }
+
+    #[test]
+    fn ci_rto_contains_rto() {
+        let a = Smry::new((100..200).map(|x| x * 2).collect(), false);
+        let b = Smry::new((100..200).collect(), false);
+        let ci = Bts::default().ci_rto(&a, Mdn, &b, Mdn, &mut Rng::new(7));
+        assert!(ci.lo < ci.hi);
+        assert!(ci.lo <= 2.0 && 2.0 <= ci.hi, "{:?}", ci);
+    }
+
+    #[test]
+    fn ci_rto_uses_sta_of_each_side() {
+        // The min of a is 100 and the median of b is 250.
+        let a = Smry::new((100..200).collect(), false);
+        let b = Smry::new((200..300).collect(), false);
+        let ci = Bts::default().ci_rto(&a, Min, &b, Mdn, &mut Rng::new(7));
+        assert!(ci.lo <= 2.5 && 2.5 <= ci.hi, "{:?}", ci);
+        assert!(ci.lo > 2.0, "{:?}", ci);
+    }
+}
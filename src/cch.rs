@@ -0,0 +1,114 @@
+//! Provides cache topology of the host.
+
+use crate::*;
+use std::fs;
+
+/// A data cache level.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct CchLvl {
+    /// Cache level, e.g. `1` for L1.
+    pub lvl: u8,
+    /// Cache size in bytes.
+    pub siz: u64,
+}
+
+/// Data cache topology of the host.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+pub struct Cch {
+    /// Data and unified cache levels, from smallest to largest.
+    pub lvls: Vec<CchLvl>,
+}
+impl Cch {
+    /// Returns cache topology read from `/sys/devices/system/cpu/cpu*/cache`.
+    ///
+    /// Instruction caches are skipped. Levels with different sizes
+    /// across processors use the smallest size.
+    pub fn read() -> Result<Self> {
+        let mut lvls: Vec<CchLvl> = Vec::new();
+        for cpu_ent in fs::read_dir("/sys/devices/system/cpu")? {
+            let cpu_pth = cpu_ent?.path();
+            let is_cpu = cpu_pth
+                .file_name()
+                .and_then(|x| x.to_str())
+                .is_some_and(|x| x.starts_with("cpu") && x[3..].parse::<u32>().is_ok());
+            if !is_cpu {
+                continue;
+            }
+            let Result::Ok(idx_ents) = fs::read_dir(cpu_pth.join("cache")) else {
+                continue;
+            };
+            for idx_ent in idx_ents {
+                let idx_pth = idx_ent?.path();
+                let Result::Ok(typ) = fs::read_to_string(idx_pth.join("type")) else {
+                    continue;
+                };
+                if typ.trim() == "Instruction" {
+                    continue;
+                }
+                let lvl: u8 = fs::read_to_string(idx_pth.join("level"))?.trim().parse()?;
+                let siz = prs_siz(&fs::read_to_string(idx_pth.join("size"))?)?;
+                match lvls.iter_mut().find(|x| x.lvl == lvl) {
+                    None => lvls.push(CchLvl { lvl, siz }),
+                    Some(x) => x.siz = x.siz.min(siz),
+                }
+            }
+        }
+        if lvls.is_empty() {
+            bail!("missing cache topology: no data caches in /sys/devices/system/cpu");
+        }
+        lvls.sort_unstable();
+        Ok(Cch { lvls })
+    }
+    /// Returns the smallest memory level a working set fits in.
+    pub fn mem(&self, byt: u64) -> Mem {
+        match self.lvls.iter().find(|x| byt <= x.siz) {
+            None => Mem::Ram,
+            Some(x) => Mem::Cch(x.lvl),
+        }
+    }
+    /// Returns a sweep of element counts below, at and above each cache level.
+    ///
+    /// Working sets are half, equal to, and double each cache size,
+    /// and span orders of magnitude, so the sweep records log spacing.
+    pub fn swp(&self, elm_siz: u32) -> Swp {
+        let elm_siz = elm_siz.max(1) as u64;
+        let mut vals: Vec<u32> = Vec::with_capacity(self.lvls.len() * 3);
+        for lvl in self.lvls.iter() {
+            for byt in [lvl.siz / 2, lvl.siz, lvl.siz * 2] {
+                vals.push((byt / elm_siz).clamp(1, u32::MAX as u64) as u32);
+            }
+        }
+        vals.sort_unstable();
+        vals.dedup();
+        Swp::LogLst(vals)
+    }
+}
+
+/// The memory level a working set fits in.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Mem {
+    /// A cache level, e.g. `Cch(1)` for L1.
+    Cch(u8),
+    /// Main memory.
+    Ram,
+}
+impl fmt::Display for Mem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mem::Cch(lvl) => write!(f, "L{}", lvl),
+            Mem::Ram => write!(f, "RAM"),
+        }
+    }
+}
+
+/// Returns bytes parsed from a sysfs cache size, e.g. `48K`.
+fn prs_siz(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let (num, mul) = match s.chars().last() {
+        Some('K') => (&s[..s.len() - 1], 1 << 10),
+        Some('M') => (&s[..s.len() - 1], 1 << 20),
+        Some('G') => (&s[..s.len() - 1], 1 << 30),
+        _ => (s, 1),
+    };
+    Ok(num.parse::<u64>()? * mul)
+}
@@ -0,0 +1,119 @@
+//! Provides cold-cache and branch predictor preparation of samples.
+
+use crate::*;
+
+/// Cache and branch predictor state before each sample.
+///
+/// The default is warm, where samples run back-to-back.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct Cld {
+    /// Data cache eviction before each sample.
+    pub evc: Evc,
+    /// Whether the branch predictor is disturbed before each sample.
+    pub bpr: bool,
+}
+impl Cld {
+    /// Returns whether samples run with warm caches and predictors.
+    pub fn is_wrm(&self) -> bool {
+        *self == Cld::default()
+    }
+}
+impl fmt::Display for Cld {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.evc, self.bpr) {
+            (Evc::Non, false) => write!(f, "warm"),
+            (Evc::Non, true) => write!(f, "cold bp"),
+            (evc, false) => write!(f, "cold {}", evc),
+            (evc, true) => write!(f, "cold {}, bp", evc),
+        }
+    }
+}
+
+/// Data cache eviction before each sample.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Evc {
+    /// No eviction.
+    #[default]
+    Non,
+    /// Streams a buffer larger than the last-level cache.
+    Str,
+    /// Flushes the registered input region with `clflush`.
+    Fls,
+}
+impl fmt::Display for Evc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Evc::Non => write!(f, "none"),
+            Evc::Str => write!(f, "stream"),
+            Evc::Fls => write!(f, "flush"),
+        }
+    }
+}
+
+/// Cache line size in bytes.
+const LNE: usize = 64;
+
+/// Eviction buffer size when the cache topology is unknown.
+const EVC_SIZ: usize = 256 << 20;
+
+/// Returns a buffer for streaming eviction.
+///
+/// The buffer is twice the last-level cache size.
+/// Bytes are non-zero so every page is backed by distinct memory.
+pub fn evc_buf(cch: Option<&Cch>) -> Vec<u8> {
+    let siz = match cch.and_then(|x| x.lvls.last()) {
+        None => EVC_SIZ,
+        Some(lvl) => lvl.siz as usize * 2,
+    };
+    vec![1u8; siz]
+}
+
+/// Evicts data caches by reading a cache line at a time.
+pub fn evc_str(buf: &[u8]) {
+    let mut sum: u8 = 0;
+    for n in (0..buf.len()).step_by(LNE) {
+        sum = sum.wrapping_add(buf[n]);
+    }
+    black_box(sum);
+}
+
+/// Flushes a memory region from all cache levels.
+///
+/// `adr` and `len` are the address and byte length of a live allocation.
+pub fn evc_fls(adr: usize, len: usize) {
+    // Safety: `clflush` is an SSE2 instruction, which x86_64 guarantees,
+    // and the caller registers an allocation outliving the sample.
+    unsafe {
+        for n in (0..len).step_by(LNE) {
+            x86_64::_mm_clflush((adr + n) as *const u8);
+        }
+        x86_64::_mm_mfence();
+    }
+}
+
+/// Count of unpredictable branches run by `dst_bpr`.
+const BPR_CNT: usize = 1 << 14;
+
+/// Disturbs branch predictor history with unpredictable branches.
+///
+/// Conditional and indirect branches are taken from pseudo-random bits.
+pub fn dst_bpr(rng: &mut Rng) {
+    let fns: [fn(u64) -> u64; 4] = [
+        |x| x.wrapping_add(1),
+        |x| x.rotate_left(7),
+        |x| x ^ 0x9E37_79B9,
+        |x| x.wrapping_mul(3),
+    ];
+    let mut acc: u64 = 0;
+    for _ in 0..BPR_CNT {
+        let x = black_box(rng.u64());
+        if x & 1 == 0 {
+            acc = acc.wrapping_add(x);
+        } else {
+            acc ^= x >> 3;
+        }
+        acc = black_box(fns[(x >> 8) as usize & 3])(acc);
+    }
+    black_box(acc);
+}
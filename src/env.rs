@@ -0,0 +1,364 @@
+//! Provides benchmark environment checks.
+
+use crate::*;
+use std::fs;
+use std::time::Duration;
+
+/// A report of the host environment read before measuring.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Env {
+    /// Distinct CPU frequency governors.
+    pub govs: Vec<String>,
+    /// Whether turbo or boost is enabled.
+    pub trb: Option<bool>,
+    /// Whether SMT is active.
+    pub smt: Option<bool>,
+    /// Count of physical cores.
+    pub cor_cnt: Option<usize>,
+    /// Count of benchmark worker threads.
+    ///
+    /// Capped at the count of `cors` when SMT is active.
+    pub thd_cnt: usize,
+    /// Logical CPUs workers are pinned to, one of each physical core, when SMT is active.
+    ///
+    /// Pinned workers never share a core, though other processes may run on siblings.
+    pub cors: Vec<usize>,
+    /// One minute load average.
+    pub lod: Option<f64>,
+    /// Whether CPUID reports an invariant timestamp counter.
+    pub inv_tsc: bool,
+    /// Hypervisor vendor when running inside a virtual machine.
+    pub vm: Option<String>,
+    /// Kernel clock source.
+    pub clk_src: Option<String>,
+    /// Warnings of checks which failed.
+    pub wrns: Vec<EnvWrn>,
+}
+impl Env {
+    /// Returns a report of the host environment for benchmark workers.
+    ///
+    /// `par` is the available parallelism, which caps worker threads.
+    /// Unreadable values are skipped rather than reported.
+    pub fn chk(par: usize) -> Self {
+        let smt = rd_str("/sys/devices/system/cpu/smt/active").map(|x| x == "1");
+        let cor_cnt = rd_cor_cnt();
+        let mut sbls = if smt == Some(true) {
+            rd_sbls()
+        } else {
+            Vec::new()
+        };
+        sbls.truncate(par);
+        let thd_cnt = if sbls.is_empty() { par } else { sbls.len() };
+        let mut env = Env {
+            govs: rd_govs(),
+            trb: rd_trb(),
+            smt,
+            cor_cnt,
+            thd_cnt,
+            cors: sbls.iter().map(|x| x.0).collect(),
+            lod: rd_str("/proc/loadavg").and_then(|x| x.split_whitespace().next()?.parse().ok()),
+            inv_tsc: cpuid_inv_tsc(),
+            vm: cpuid_vm(),
+            clk_src: rd_str("/sys/devices/system/clocksource/clocksource0/current_clocksource"),
+            wrns: Vec::new(),
+        };
+
+        // Check each reading.
+        for gov in env.govs.iter().filter(|x| *x != "performance") {
+            env.wrns.push(EnvWrn::Gov(gov.clone()));
+        }
+        if env.trb == Some(true) {
+            env.wrns.push(EnvWrn::Trb);
+        }
+        if let Some(lod) = env.lod {
+            if lod > LOD_MAX * thd_cnt.max(1) as f64 {
+                env.wrns.push(EnvWrn::Lod(lod));
+            }
+        }
+        if !env.inv_tsc {
+            env.wrns.push(EnvWrn::InvTsc);
+        }
+        if let Some(vm) = env.vm.as_ref() {
+            if env.clk_src.as_deref() != Some("tsc") {
+                env.wrns.push(EnvWrn::VmTsc(vm.clone()));
+            }
+        }
+        if !sbls.is_empty() {
+            // Sample load of the siblings of worker cores.
+            let fst = rd_stat();
+            std::thread::sleep(SBL_DUR);
+            let lst = rd_stat();
+            let mut bsys: Vec<usize> = sbls
+                .iter()
+                .flat_map(|x| x.1.iter().copied())
+                .filter(|x| bsy(&fst, &lst, *x) > SBL_MAX)
+                .collect();
+            bsys.sort_unstable();
+            if !bsys.is_empty() {
+                env.wrns.push(EnvWrn::SmtBsy(bsys));
+            }
+        }
+        env
+    }
+}
+
+/// Load average per worker thread above which the host is busy.
+const LOD_MAX: f64 = 0.25;
+
+/// Busy fraction above which an SMT sibling of a worker core is busy.
+const SBL_MAX: f64 = 0.25;
+
+/// Interval over which SMT sibling load is sampled.
+const SBL_DUR: Duration = Duration::from_millis(100);
+
+/// A failed environment check.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnvWrn {
+    /// A CPU frequency governor other than `performance`.
+    Gov(String),
+    /// Turbo or boost is enabled.
+    Trb,
+    /// A high one minute load average.
+    Lod(f64),
+    /// CPUID doesn't report an invariant timestamp counter.
+    InvTsc,
+    /// A virtual machine where the kernel doesn't use the timestamp counter.
+    VmTsc(String),
+    /// Busy SMT siblings of worker cores, as logical CPUs.
+    SmtBsy(Vec<usize>),
+}
+impl fmt::Display for EnvWrn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EnvWrn::Gov(gov) => write!(f, "cpu frequency governor is '{}', not 'performance'", gov),
+            EnvWrn::Trb => write!(f, "turbo boost is enabled; frequencies vary"),
+            EnvWrn::Lod(lod) => write!(f, "load average {:.2} is high", lod),
+            EnvWrn::InvTsc => write!(
+                f,
+                "cpuid reports no invariant tsc; cycles vary with frequency"
+            ),
+            EnvWrn::VmTsc(vm) => write!(
+                f,
+                "running in a '{}' virtual machine without a stable tsc",
+                vm
+            ),
+            EnvWrn::SmtBsy(cpus) => write!(
+                f,
+                "smt siblings {:?} of worker cores are busy; workers share cores",
+                cpus
+            ),
+        }
+    }
+}
+
+/// Returns the trimmed contents of a file.
+fn rd_str(pth: &str) -> Option<String> {
+    fs::read_to_string(pth).ok().map(|x| x.trim().to_string())
+}
+
+/// Returns distinct frequency governors of all processors.
+fn rd_govs() -> Vec<String> {
+    let mut govs: Vec<String> = Vec::new();
+    let Result::Ok(ents) = fs::read_dir("/sys/devices/system/cpu") else {
+        return govs;
+    };
+    for ent in ents.flatten() {
+        let pth = ent.path().join("cpufreq/scaling_governor");
+        if let Some(gov) = pth.to_str().and_then(rd_str) {
+            if !govs.contains(&gov) {
+                govs.push(gov);
+            }
+        }
+    }
+    govs.sort_unstable();
+    govs
+}
+
+/// Returns whether turbo or boost is enabled.
+fn rd_trb() -> Option<bool> {
+    // Intel reports disabled turbo, other drivers report enabled boost.
+    if let Some(no_trb) = rd_str("/sys/devices/system/cpu/intel_pstate/no_turbo") {
+        return Some(no_trb == "0");
+    }
+    rd_str("/sys/devices/system/cpu/cpufreq/boost").map(|x| x == "1")
+}
+
+/// Returns the count of distinct physical cores.
+fn rd_cor_cnt() -> Option<usize> {
+    let mut cors: HashSet<(String, String)> = HashSet::new();
+    for ent in fs::read_dir("/sys/devices/system/cpu").ok()?.flatten() {
+        let pth = ent.path().join("topology");
+        let pkg = rd_str(pth.join("physical_package_id").to_str()?);
+        let cor = rd_str(pth.join("core_id").to_str()?);
+        if let (Some(pkg), Some(cor)) = (pkg, cor) {
+            cors.insert((pkg, cor));
+        }
+    }
+    if cors.is_empty() {
+        None
+    } else {
+        Some(cors.len())
+    }
+}
+
+/// Returns one allowed logical CPU of each physical core, with its SMT siblings.
+///
+/// Cores are read from `thread_siblings_list`, and ordered by logical CPU.
+fn rd_sbls() -> Vec<(usize, Vec<usize>)> {
+    let alw = rd_alw();
+    let mut sets: Vec<Vec<usize>> = Vec::new();
+    let Result::Ok(ents) = fs::read_dir("/sys/devices/system/cpu") else {
+        return Vec::new();
+    };
+    for ent in ents.flatten() {
+        let pth = ent.path().join("topology/thread_siblings_list");
+        if let Some(set) = pth.to_str().and_then(rd_str).and_then(|x| prs_cpus(&x)) {
+            if !sets.contains(&set) {
+                sets.push(set);
+            }
+        }
+    }
+    let mut sbls: Vec<(usize, Vec<usize>)> = Vec::with_capacity(sets.len());
+    for set in sets {
+        let Some(cpu) = set.iter().copied().find(|x| alw.contains(x)) else {
+            continue;
+        };
+        sbls.push((cpu, set.into_iter().filter(|x| *x != cpu).collect()));
+    }
+    sbls.sort_unstable();
+    sbls
+}
+
+/// Returns logical CPUs of a list, e.g. `0-3,8`.
+fn prs_cpus(s: &str) -> Option<Vec<usize>> {
+    let mut cpus: Vec<usize> = Vec::new();
+    for rng in s.split(',') {
+        match rng.split_once('-') {
+            Some((lo, hi)) => cpus.extend(lo.parse::<usize>().ok()?..=hi.parse().ok()?),
+            None => cpus.push(rng.parse().ok()?),
+        }
+    }
+    cpus.sort_unstable();
+    Some(cpus)
+}
+
+/// Returns logical CPUs the process may run on.
+fn rd_alw() -> Vec<usize> {
+    // Safety: `sched_getaffinity` writes a zeroed set owned by the caller.
+    unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+        if libc::sched_getaffinity(0, mem::size_of::<libc::cpu_set_t>(), &mut set) != 0 {
+            return Vec::new();
+        }
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|x| libc::CPU_ISSET(*x, &set))
+            .collect()
+    }
+}
+
+/// Pins the calling thread to a logical CPU.
+///
+/// Returns false when the CPU isn't allowed.
+pub fn pin(cpu: usize) -> bool {
+    // Safety: `sched_setaffinity` reads a set owned by the caller.
+    unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+        libc::CPU_SET(cpu, &mut set);
+        libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
+
+/// Returns busy and total ticks of each logical CPU from `/proc/stat`.
+fn rd_stat() -> HashMap<usize, (u64, u64)> {
+    rd_str("/proc/stat")
+        .map(|x| prs_stat(&x))
+        .unwrap_or_default()
+}
+
+/// Returns busy and total ticks of each logical CPU of `/proc/stat` contents.
+///
+/// Idle and I/O wait ticks aren't busy. Guest ticks are already counted as user ticks.
+fn prs_stat(s: &str) -> HashMap<usize, (u64, u64)> {
+    let mut stat: HashMap<usize, (u64, u64)> = HashMap::new();
+    for lin in s.lines() {
+        let mut cols = lin.split_whitespace();
+        let Some(cpu) = cols
+            .next()
+            .and_then(|x| x.strip_prefix("cpu")?.parse().ok())
+        else {
+            continue;
+        };
+        let tcks: Vec<u64> = cols.take(8).filter_map(|x| x.parse().ok()).collect();
+        if tcks.len() < 5 {
+            continue;
+        }
+        let tot: u64 = tcks.iter().sum();
+        stat.insert(cpu, (tot - tcks[3] - tcks[4], tot));
+    }
+    stat
+}
+
+/// Returns the busy fraction of a logical CPU between two readings.
+fn bsy(fst: &HashMap<usize, (u64, u64)>, lst: &HashMap<usize, (u64, u64)>, cpu: usize) -> f64 {
+    match (fst.get(&cpu), lst.get(&cpu)) {
+        (Some(a), Some(b)) if b.1 > a.1 => b.0.saturating_sub(a.0) as f64 / (b.1 - a.1) as f64,
+        _ => 0.0,
+    }
+}
+
+/// Returns whether CPUID reports an invariant timestamp counter.
+fn cpuid_inv_tsc() -> bool {
+    if x86_64::__cpuid(0x8000_0000).eax < 0x8000_0007 {
+        return false;
+    }
+    x86_64::__cpuid(0x8000_0007).edx & (1 << 8) != 0
+}
+
+/// Returns the hypervisor vendor when CPUID reports a hypervisor.
+fn cpuid_vm() -> Option<String> {
+    if x86_64::__cpuid(1).ecx & (1 << 31) == 0 {
+        return None;
+    }
+    // The hypervisor leaf holds a twelve byte vendor name.
+    let r = x86_64::__cpuid(0x4000_0000);
+    let mut byts: Vec<u8> = Vec::with_capacity(12);
+    for reg in [r.ebx, r.ecx, r.edx] {
+        byts.extend(reg.to_le_bytes());
+    }
+    let vnd = String::from_utf8_lossy(&byts)
+        .trim_matches(char::from(0))
+        .trim()
+        .to_string();
+    Some(if vnd.is_empty() {
+        "unknown".into()
+    } else {
+        vnd
+    })
+}
+
+#[cfg(test)]
+mod tst {
+    use super::*;
+
+    #[test]
+    fn prs_cpus_lst() {
+        assert_eq!(prs_cpus("0"), Some(vec![0]));
+        assert_eq!(prs_cpus("0,4"), Some(vec![0, 4]));
+        assert_eq!(prs_cpus("8,0-2"), Some(vec![0, 1, 2, 8]));
+        assert_eq!(prs_cpus("x"), None);
+    }
+
+    #[test]
+    fn prs_stat_bsy() {
+        let fst = prs_stat(
+            "cpu  30 0 10 60 0 0 0 0 0 0\ncpu0 20 0 5 75 0 0 0 0 0 0\ncpu1 10 0 5 80 5 0 0 0 0 0\n",
+        );
+        let lst = prs_stat("cpu0 80 0 5 115 0 0 0 0 0 0\ncpu1 10 0 5 170 15 0 0 0 0 0\n");
+        assert_eq!(fst.len(), 2);
+        assert_eq!(fst[&1], (15, 100));
+        // cpu0 spent 60 of 100 ticks busy, and cpu1 idled.
+        assert!((bsy(&fst, &lst, 0) - 0.6).abs() < 1e-9);
+        assert_eq!(bsy(&fst, &lst, 1), 0.0);
+        assert_eq!(bsy(&fst, &lst, 2), 0.0);
+    }
+}
@@ -0,0 +1,224 @@
+//! Provides asymptotic complexity fitting over struct label series.
+
+use crate::*;
+
+/// An asymptotic complexity model.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Cpx {
+    /// Constant, `O(1)`.
+    #[default]
+    O1,
+    /// Logarithmic, `O(log n)`.
+    OLogN,
+    /// Linear, `O(n)`.
+    ON,
+    /// Linearithmic, `O(n log n)`.
+    ONLogN,
+    /// Quadratic, `O(n²)`.
+    ON2,
+    /// Cubic, `O(n³)`.
+    ON3,
+}
+impl Cpx {
+    /// Every complexity model.
+    pub const ALL: [Cpx; 6] = [
+        Cpx::O1,
+        Cpx::OLogN,
+        Cpx::ON,
+        Cpx::ONLogN,
+        Cpx::ON2,
+        Cpx::ON3,
+    ];
+
+    /// Returns the model's growth function at `n`.
+    #[inline]
+    pub fn f(&self, n: f64) -> f64 {
+        match self {
+            Cpx::O1 => 1.0,
+            Cpx::OLogN => n.max(2.0).log2(),
+            Cpx::ON => n,
+            Cpx::ONLogN => n * n.max(2.0).log2(),
+            Cpx::ON2 => n * n,
+            Cpx::ON3 => n * n * n,
+        }
+    }
+}
+impl fmt::Display for Cpx {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cpx::O1 => write!(f, "O(1)"),
+            Cpx::OLogN => write!(f, "O(log n)"),
+            Cpx::ON => write!(f, "O(n)"),
+            Cpx::ONLogN => write!(f, "O(n log n)"),
+            Cpx::ON2 => write!(f, "O(n²)"),
+            Cpx::ON3 => write!(f, "O(n³)"),
+        }
+    }
+}
+
+/// A least-squares fit of a complexity model, `y = coef * f(n)`.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct CpxFit {
+    /// Complexity model.
+    pub cpx: Cpx,
+    /// Fitted coefficient in cycles per unit of `f(n)`.
+    pub coef: f64,
+    /// Root-mean-square error relative to the mean value.
+    pub rms: f64,
+    /// Coefficient of determination.
+    pub r2: f64,
+}
+impl CpxFit {
+    /// Returns a least-squares fit of a complexity model.
+    pub fn new(cpx: Cpx, ns: &[f64], ys: &[f64]) -> Self {
+        let fs: Vec<f64> = ns.iter().map(|n| cpx.f(*n)).collect();
+        let fy: f64 = fs.iter().zip(ys.iter()).map(|(f, y)| f * y).sum();
+        let ff: f64 = fs.iter().map(|f| f * f).sum();
+        let coef = if ff > 0.0 { fy / ff } else { 0.0 };
+
+        let len = ys.len().max(1) as f64;
+        let mean = ys.iter().sum::<f64>() / len;
+        let ss_res: f64 = fs
+            .iter()
+            .zip(ys.iter())
+            .map(|(f, y)| (y - coef * f).powi(2))
+            .sum();
+        let ss_tot: f64 = ys.iter().map(|y| (y - mean).powi(2)).sum();
+        let rms = if mean > 0.0 {
+            (ss_res / len).sqrt() / mean
+        } else {
+            0.0
+        };
+        let r2 = if ss_tot > 0.0 {
+            1.0 - ss_res / ss_tot
+        } else {
+            1.0
+        };
+        CpxFit { cpx, coef, rms, r2 }
+    }
+
+    /// Returns the fitted value at `n`.
+    #[inline]
+    pub fn at(&self, n: f64) -> f64 {
+        self.coef * self.cpx.f(n)
+    }
+}
+
+/// Complexity fits of a selection's series over a struct label.
+#[derive(Debug)]
+pub struct Fit<L>
+where
+    L: Label,
+{
+    pub hdr_lbls: Vec<L>,
+    pub lbls: Vec<L>,
+    pub sta: Sta,
+    pub mtr: Mtr,
+    pub cld: Cld,
+    pub gen: SelGen,
+    /// Struct label values, `n`.
+    pub ns: Vec<f64>,
+    /// Statistical values at each `n`.
+    pub vals: Vec<u64>,
+    /// Cycles per unit of `n`.
+    pub per_ns: Vec<f64>,
+    /// Fits of every complexity model, from best to worst.
+    pub fits: Vec<CpxFit>,
+}
+impl<L> Fit<L>
+where
+    L: Label,
+{
+    /// Returns complexity fits of a selection.
+    ///
+    /// Selection values are expected to have numeric struct labels, e.g. `Len(u32)`.
+    /// Values with other labels, e.g. `Ty(Typ)`, are skipped.
+    /// Returns `None` when no values have numeric labels.
+    pub fn new(sel: &Sel<L>) -> Option<Self> {
+        let mut hdr_lbls: Vec<L> = Vec::with_capacity(sel.vals.len());
+        let mut ns: Vec<f64> = Vec::with_capacity(sel.vals.len());
+        let mut vals: Vec<u64> = Vec::with_capacity(sel.vals.len());
+        for sta_val in sel.vals.iter() {
+            if let Result::Ok(n) = sta_val.lbl.val() {
+                hdr_lbls.push(sta_val.lbl);
+                ns.push(n as f64);
+                vals.push(sta_val.val);
+            }
+        }
+        if ns.is_empty() {
+            return None;
+        }
+        let ys: Vec<f64> = vals.iter().map(|x| *x as f64).collect();
+        let per_ns: Vec<f64> = ns
+            .iter()
+            .zip(ys.iter())
+            .map(|(n, y)| y / n.max(1.0))
+            .collect();
+
+        // Fit every model, and order by relative error.
+        let mut fits: Vec<CpxFit> = Cpx::ALL.iter().map(|x| CpxFit::new(*x, &ns, &ys)).collect();
+        fits.sort_by(|a, b| a.rms.total_cmp(&b.rms));
+
+        Some(Fit {
+            hdr_lbls,
+            lbls: sel.lbls.clone(),
+            sta: sel.sta,
+            mtr: sel.mtr,
+            cld: sel.cld,
+            gen: sel.gen,
+            ns,
+            vals,
+            per_ns,
+            fits,
+        })
+    }
+
+    /// Returns the best fit.
+    #[inline]
+    pub fn best(&self) -> &CpxFit {
+        &self.fits[0]
+    }
+}
+
+#[cfg(test)]
+mod tst {
+    use super::*;
+
+    /// Returns the best fitting model of a synthetic series.
+    fn bst(ns: &[f64], g: impl Fn(f64) -> f64) -> Cpx {
+        // Perturb values by up to 3% to resemble measurements.
+        let ys: Vec<f64> = ns
+            .iter()
+            .enumerate()
+            .map(|(i, n)| g(*n) * (1.0 + 0.03 * ((i % 3) as f64 - 1.0)))
+            .collect();
+        Cpx::ALL
+            .iter()
+            .map(|x| CpxFit::new(*x, ns, &ys))
+            .min_by(|a, b| a.rms.total_cmp(&b.rms))
+            .unwrap()
+            .cpx
+    }
+
+    #[test]
+    fn fit_cpx() {
+        let ns: Vec<f64> = (4..=16).map(|x| (1u64 << x) as f64).collect();
+        assert_eq!(bst(&ns, |n| 3.0 * n * n.log2()), Cpx::ONLogN);
+        assert_eq!(bst(&ns, |n| 5.0 * n + 40.0), Cpx::ON);
+        assert_eq!(bst(&ns, |n| 0.5 * n * n), Cpx::ON2);
+        assert_eq!(bst(&ns, |n| 20.0 * n.log2()), Cpx::OLogN);
+        assert_eq!(bst(&ns, |_| 120.0), Cpx::O1);
+    }
+
+    #[test]
+    fn fit_coef() {
+        let ns = [16.0, 256.0, 4096.0];
+        let ys: Vec<f64> = ns.iter().map(|n| 3.0 * n * f64::log2(*n)).collect();
+        let fit = CpxFit::new(Cpx::ONLogN, &ns, &ys);
+        assert!((fit.coef - 3.0).abs() < 1e-9);
+        assert!(fit.rms < 1e-9);
+        assert!((fit.r2 - 1.0).abs() < 1e-9);
+        assert!((fit.at(1024.0) - 30_720.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,189 @@
+//! Provides seeded input data generators.
+
+use crate::*;
+
+/// A distribution pattern of generated input data.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Pat {
+    /// Uniformly random values.
+    #[default]
+    Rnd,
+    /// Random values sorted ascending.
+    Srt,
+    /// Random values sorted descending.
+    Rev,
+    /// Sorted random values with about one percent of positions swapped.
+    NrlSrt,
+    /// A single repeated value.
+    Eql,
+    /// Zipfian ranks, where rank `k` occurs in proportion to `1 / k`.
+    Zpf,
+    /// Ascending runs of about `√len` values which restart at zero.
+    Saw,
+}
+impl Pat {
+    /// Every pattern.
+    pub const ALL: [Pat; 7] = [
+        Pat::Rnd,
+        Pat::Srt,
+        Pat::Rev,
+        Pat::NrlSrt,
+        Pat::Eql,
+        Pat::Zpf,
+        Pat::Saw,
+    ];
+}
+impl fmt::Display for Pat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pat::Rnd => write!(f, "rnd"),
+            Pat::Srt => write!(f, "srt"),
+            Pat::Rev => write!(f, "rev"),
+            Pat::NrlSrt => write!(f, "nrl_srt"),
+            Pat::Eql => write!(f, "eql"),
+            Pat::Zpf => write!(f, "zpf"),
+            Pat::Saw => write!(f, "saw"),
+        }
+    }
+}
+
+/// A value which input generators produce.
+pub trait GenVal: Copy + PartialOrd + Send + 'static {
+    /// Returns a value converted from a generated `u64`.
+    fn frm(x: u64) -> Self;
+}
+macro_rules! gen_val {
+    ($($typ:ty),+) => {
+        $(impl GenVal for $typ {
+            #[inline]
+            fn frm(x: u64) -> Self {
+                x as $typ
+            }
+        })+
+    };
+}
+gen_val!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+/// A seeded input data generator.
+///
+/// Identical patterns and seeds generate identical data on every host
+/// and Rust release.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct Gen {
+    pub pat: Pat,
+    pub sed: u64,
+}
+impl Gen {
+    pub fn new(pat: Pat, sed: u64) -> Self {
+        Gen { pat, sed }
+    }
+    /// Returns generated input data of a length.
+    pub fn gen<T>(&self, len: usize) -> Vec<T>
+    where
+        T: GenVal,
+    {
+        let mut rng = Rng::new_u64(self.sed, self.pat as u64);
+        let mut rnd = |len: usize| -> Vec<T> { (0..len).map(|_| T::frm(rng.u64())).collect() };
+        match self.pat {
+            Pat::Rnd => rnd(len),
+            Pat::Srt => {
+                let mut vals = rnd(len);
+                srt(&mut vals);
+                vals
+            }
+            Pat::Rev => {
+                let mut vals = rnd(len);
+                srt(&mut vals);
+                vals.reverse();
+                vals
+            }
+            Pat::NrlSrt => {
+                let mut vals = rnd(len);
+                srt(&mut vals);
+                // Swap about one percent of positions.
+                let mut rng = Rng::new_u64(mix(self.sed, self.pat as u64), len as u64);
+                for _ in 0..(len / 100).max(1).min(len / 2) {
+                    let a = rng.below(len as u64) as usize;
+                    let b = rng.below(len as u64) as usize;
+                    vals.swap(a, b);
+                }
+                vals
+            }
+            Pat::Eql => vec![T::frm(rng.u64()); len],
+            Pat::Zpf => {
+                // Sample ranks from the inverse cumulative distribution.
+                let mut cum: Vec<f64> = Vec::with_capacity(len);
+                let mut sum = 0f64;
+                for k in 1..=len {
+                    sum += 1.0 / k as f64;
+                    cum.push(sum);
+                }
+                (0..len)
+                    .map(|_| {
+                        let x = rng.f64() * sum;
+                        let rnk = cum.partition_point(|c| *c < x);
+                        T::frm(rnk.min(len.saturating_sub(1)) as u64 + 1)
+                    })
+                    .collect()
+            }
+            Pat::Saw => {
+                let per = ((len as f64).sqrt().ceil() as usize).max(1);
+                (0..len).map(|n| T::frm((n % per) as u64)).collect()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Gen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, seed {}", self.pat, self.sed)
+    }
+}
+
+/// Input data generators shared by the benchmarks of a selection.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub enum SelGen {
+    /// No benchmark generates inputs, or generators differ.
+    #[default]
+    Non,
+    /// Every benchmark generates inputs with one generator.
+    Gen(Gen),
+    /// Every benchmark generates inputs with one seed,
+    /// and patterns differ, e.g. as a label dimension.
+    Sed(u64),
+}
+impl SelGen {
+    /// Returns the generators shared by benchmarks.
+    pub fn new(mut gens: impl Iterator<Item = Option<Gen>>) -> Self {
+        let Some(Some(fst)) = gens.next() else {
+            return SelGen::Non;
+        };
+        let mut ret = SelGen::Gen(fst);
+        for gen in gens {
+            match gen {
+                Some(x) if x == fst => {}
+                Some(x) if x.sed == fst.sed => ret = SelGen::Sed(fst.sed),
+                _ => return SelGen::Non,
+            }
+        }
+        ret
+    }
+}
+impl fmt::Display for SelGen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SelGen::Non => fmt::Result::Ok(()),
+            SelGen::Gen(x) => write!(f, "{}", x),
+            SelGen::Sed(x) => write!(f, "seed {}", x),
+        }
+    }
+}
+
+/// Sorts generated values ascending.
+fn srt<T>(vals: &mut [T])
+where
+    T: GenVal,
+{
+    vals.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+}
@@ -1,16 +1,13 @@
 #![allow(clippy::new_without_default)]
-#![feature(fn_ptr_trait)]
 
 use anyhow::{bail, Ok, Result};
 use itr::rngs;
-use sptr::OpaqueFnPtr;
 use std::cell::RefCell;
-use std::marker::PhantomData;
 use std::rc::Rc;
-use std::sync::mpsc::channel;
+use std::sync::{mpsc::channel, Arc, Mutex};
 use std::{
     arch::x86_64,
-    collections::{hash_map::DefaultHasher, HashMap},
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
     fmt::{Debug, Display},
     hash::{Hash, Hasher},
     hint::black_box,
@@ -19,7 +16,35 @@ use std::{
 use std::{fmt, thread};
 use threadpool::ThreadPool;
 use Sta::*;
+mod bts;
+mod cch;
+mod cld;
+mod env;
+mod fit;
+mod gen;
+mod rfl;
+mod rnd;
+mod rus;
+mod sig;
+mod smry;
+mod swp;
 mod tbl;
+mod thr;
+mod typ;
+pub use bts::*;
+pub use cch::*;
+pub use cld::*;
+pub use env::*;
+pub use fit::*;
+pub use gen::*;
+pub use rfl::*;
+pub use rnd::*;
+pub use rus::*;
+pub use sig::*;
+pub use smry::*;
+pub use swp::*;
+pub use thr::*;
+pub use typ::*;
 
 /// A benchmark study.
 #[derive(Debug)]
@@ -38,7 +63,13 @@ where
             reg_blds: HashMap::new(),
         }
     }
-    pub fn reg_bld(&mut self, lbls: &[L], f: fn(&mut RegBld<L>)) -> &mut Self {
+    /// Registers a builder which inserts benchmark functions for labels.
+    ///
+    /// The builder is called at each run, and may be a capturing closure.
+    pub fn reg_bld<F>(&mut self, lbls: &[L], f: F) -> &mut Self
+    where
+        F: FnMut(&mut RegBld<L>) + 'static,
+    {
         if !lbls.is_empty() {
             let reg_bld = RegBld::new(lbls, f);
             self.reg_blds.entry(reg_bld.id).or_insert(reg_bld);
@@ -51,12 +82,43 @@ where
         // println!("qry_bld.sel_blds:{}", qry_bld.sel_blds.len());
         // println!("qry_bld.cmp_blds:{}", qry_bld.cmp_blds.len());
 
-        // Create a runtime query from the build query.
-        let qry = Qry::new();
+        // Read resource usage only when context switches are checked,
+        // resource usage is reported, or a selection reads it.
+        // Check resource usage is readable before workers read it around samples.
+        let rd_rus = qry_bld.csw != Csw::Off
+            || qry_bld.rus
+            || qry_bld.sel_blds.values().any(|x| x.mtr.rus_idx().is_some());
+        if rd_rus {
+            Rus::thd()?;
+        }
+
+        // Check the host is fit for benchmarking before measuring.
+        // Strict queries refuse to run with failed checks.
+        // Workers run on physical cores when SMT is active.
+        let env = Env::chk(thread::available_parallelism().unwrap().into());
+        let thd_cnt = env.thd_cnt;
+        if qry_bld.stc && !env.wrns.is_empty() {
+            bail!(
+                "unfit environment: {}",
+                env.wrns
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            );
+        }
+        for wrn in env.wrns.iter() {
+            println!("warning: {}", wrn);
+        }
 
         // Create benchmark functions from the build registry.
+        // Registrations shared by multiple selections are inserted once.
         let mut ben_blds: Vec<BenBld<L>> = Vec::with_capacity(qry_bld.sel_blds.len() * 16);
+        let mut reg_ids: HashSet<u64> = HashSet::with_capacity(qry_bld.sel_blds.len());
         for (_, sel_bld) in qry_bld.sel_blds.iter() {
+            if !reg_ids.insert(sel_bld.reg_id()) {
+                continue;
+            }
             match self.reg_blds.get_mut(&sel_bld.reg_id()) {
                 None => bail!(
                     "build registry: missing selection '{}'",
@@ -90,6 +152,18 @@ where
                         }
                     }
 
+                    // Validate throughput counts and flushed input regions.
+                    for ben_bld in reg_bld.ben_blds.iter() {
+                        ben_bld.cnts()?;
+                        if ben_bld.cld.evc == Evc::Fls && ben_bld.fls.is_none() {
+                            bail!(
+                                "missing flush region: benchmark '{}' of registry build '{}' flushes without a registered input",
+                                ben_bld.lbl,
+                                join(&sel_bld.lbls, ',')
+                            );
+                        }
+                    }
+
                     // Store benchmark functions.
                     ben_blds.extend(reg_bld.ben_blds.drain(0..));
                 }
@@ -97,38 +171,184 @@ where
         }
         // println!("    ben_blds:{:?}", ben_blds);
 
-        // Run benchmark functions in parallel.
+        // Allocate a shared eviction buffer for cold benchmarks.
+        let cch = Cch::read().ok();
+        let evc_buf: Option<Arc<Vec<u8>>> = if ben_blds.iter().any(|x| x.cld.evc == Evc::Str) {
+            Some(Arc::new(evc_buf(cch.as_ref())))
+        } else {
+            None
+        };
+
+        // Run cold benchmarks in their own phase after warm benchmarks.
+        // Cold preparation evicts shared caches, which would disturb
+        // warm benchmarks running on other workers.
         let ben_cnt = ben_blds.len();
-        let thd_cnt = thread::available_parallelism().unwrap().into();
+        let (cld_ben_blds, mut ben_blds): (Vec<BenBld<L>>, Vec<BenBld<L>>) =
+            ben_blds.into_iter().partition(|x| !x.cld.is_wrm());
+
+        // Group warm benchmarks run by each worker.
+        // Interleaved schedules group benchmarks of the same column,
+        // which places compared benchmarks in the same worker.
+        let mut grps: Vec<Vec<BenBld<L>>> = Vec::new();
+        if qry_bld.sch == Sch::Seq {
+            for rng in rngs(thd_cnt, ben_blds.len()) {
+                grps.push(ben_blds.drain(0..rng.len()).collect());
+            }
+        } else {
+            let mut col_lbls: Vec<L> = ben_blds.iter().map(|x| x.lbl).collect();
+            col_lbls.sort_unstable();
+            col_lbls.dedup();
+            let mut cols: Vec<Vec<BenBld<L>>> = col_lbls.iter().map(|_| Vec::new()).collect();
+            for ben_bld in ben_blds.drain(0..) {
+                let idx = col_lbls.binary_search(&ben_bld.lbl).unwrap();
+                cols[idx].push(ben_bld);
+            }
+            for rng in rngs(thd_cnt, cols.len()) {
+                grps.push(cols.drain(0..rng.len()).flatten().collect());
+            }
+        }
+
+        // Run benchmark functions in parallel.
         let pool = ThreadPool::new(thd_cnt);
         let (tx, rx) = channel();
+        let exc_otl = qry_bld.exc_otl;
+        let sch = qry_bld.sch;
+        let csw = qry_bld.csw;
         // println!("thd_cnt:{}, ben_cnt:{}", thd_cnt, ben_cnt);
-        for rng in rngs(thd_cnt, ben_cnt) {
-            let rng_ben_blds: Vec<BenBld<L>> = ben_blds.drain(0..rng.len()).collect();
-            // println!("rng_ben_blds:{}", rng_ben_blds.len());
-            let tx = tx.clone();
-            pool.execute(move || {
-                // Calculate the overhead of running the CPU timestamp instructions.
-                // Subtracting the overhead produces a more accurate measurement.
-                let overhead = overhead_cpu_cyc();
-
-                for ben_bld in rng_ben_blds {
-                    let mut vals: Vec<u64> = Vec::with_capacity(itr as usize);
-
-                    // Record benchmark function multiple times.
-                    // Benchmark times vary at each iteration.
-                    for _ in 0..itr {
-                        let ellapsed = ben_bld.run();
-                        vals.push(ellapsed - overhead);
+        // Workers take a free core from the pool, so pinned workers never share a core.
+        let cors = Arc::new(Mutex::new(env.cors.clone()));
+        let run_grp = move |grp_idx: usize, mut grp_ben_blds: Vec<BenBld<L>>| {
+            let cor = cors.lock().unwrap().pop();
+            if let Some(cor) = cor {
+                pin(cor);
+            }
+
+            // Calculate the overhead of running the CPU timestamp instructions.
+            // Subtracting the overhead produces a more accurate measurement.
+            let ovh = Ovh::cal();
+            let overhead = ovh.min;
+
+            let cnt = grp_ben_blds.len();
+            let mut valss: Vec<Vec<u64>> = vec![Vec::with_capacity(itr as usize); cnt];
+            let mut drp_valss: Vec<Vec<u64>> = vec![Vec::new(); cnt];
+            let mut cswss: Vec<Vec<bool>> = vec![Vec::new(); cnt];
+            let mut rus_valsss: Vec<Vec<Vec<u64>>> = if rd_rus {
+                vec![vec![Vec::with_capacity(itr as usize); Mtr::RUS.len()]; cnt]
+            } else {
+                vec![Vec::new(); cnt]
+            };
+            let mut rus_sums: Vec<RusVals> = vec![RusVals::default(); cnt];
+            // Resource usage baselines of preparation alone, read before the first sample.
+            let mut bses: Vec<Option<RusVals>> = vec![None; cnt];
+            let mut csw_cnts: Vec<u32> = vec![0; cnt];
+            let mut smp_cnts: Vec<u32> = vec![0; cnt];
+            let mut bpr_rngs: Vec<Rng> = grp_ben_blds
+                .iter()
+                .map(|x| Rng::new_mix(0, x.lbl))
+                .collect();
+
+            // Record benchmark functions multiple times in schedule order.
+            // Benchmark times vary at each iteration.
+            // Batched cycles are averaged per call.
+            for idx in sch.ord(cnt, itr, grp_idx) {
+                let ben_bld = &mut grp_ben_blds[idx];
+                let bat = ben_bld.bat as u64;
+
+                // Prepare cold samples after setup, outside the timed region.
+                let (cld, fls) = (ben_bld.cld, ben_bld.fls);
+                let bpr_rng = &mut bpr_rngs[idx];
+                let mut prp = || {
+                    match (cld.evc, evc_buf.as_ref(), fls) {
+                        (Evc::Str, Some(buf), _) => evc_str(buf),
+                        (Evc::Fls, _, Some((adr, len))) => evc_fls(adr, len),
+                        _ => {}
                     }
+                    if cld.bpr {
+                        dst_bpr(bpr_rng);
+                    }
+                };
 
-                    // Send the benchmark results back to the main thread.
-                    let ben = Ben::new(ben_bld.lbl, vals);
-                    if let Err(e) = tx.send((ben_bld.reg_id, ben)) {
-                        println!("send ben error: {:?}", e);
+                // Read resource usage and check context switches around the timed region.
+                // Discarded samples run again up to a limit.
+                let mut smp = Smp::new(&mut prp, rd_rus);
+                let bse = *bses[idx].get_or_insert_with(|| smp.bse());
+                let mut tri: u32 = 0;
+                let (ellapsed, drp_ellapsed, pmt, rus) = loop {
+                    let (ellapsed, drp_ellapsed) = ben_bld.run(&mut smp);
+                    let (fst, lst) = (smp.fst, smp.lst);
+                    let pmt = csw != Csw::Off && lst.csw() != fst.csw();
+                    let dlt = lst.dlt(&fst);
+                    let rus: RusVals = std::array::from_fn(|n| dlt[n].saturating_sub(bse[n]));
+                    // Sum resource usage of every sample run, including discarded samples.
+                    // The baseline is subtracted from sums once all samples run.
+                    for (sum, val) in rus_sums[idx].iter_mut().zip(dlt) {
+                        *sum += val;
                     }
+                    tri += 1;
+                    if pmt {
+                        csw_cnts[idx] += 1;
+                    }
+                    if !pmt || csw != Csw::Dsc || tri == CSW_TRI {
+                        break (ellapsed, drp_ellapsed, pmt, rus);
+                    }
+                };
+                smp_cnts[idx] += tri;
+                if csw != Csw::Off {
+                    cswss[idx].push(pmt);
                 }
-            });
+                for (rus_vals, val) in rus_valsss[idx].iter_mut().zip(rus) {
+                    rus_vals.push(val);
+                }
+
+                valss[idx].push(ellapsed.saturating_sub(overhead) / bat);
+                if ben_bld.drp == Drp::Sep {
+                    drp_valss[idx].push(drp_ellapsed.saturating_sub(overhead) / bat);
+                }
+            }
+
+            for (idx, ben_bld) in grp_ben_blds.into_iter().enumerate() {
+                // Send the benchmark results back to the main thread.
+                let bat = ben_bld.bat as u64;
+                let drp_vals = mem::take(&mut drp_valss[idx]);
+                let mut ben = Ben::new(ben_bld.lbl, mem::take(&mut valss[idx]), exc_otl);
+                ben.cnts = ben_bld.cnts().unwrap();
+                ben.gen = ben_bld.gen;
+                ben.cld = ben_bld.cld;
+                ben.csws = mem::take(&mut cswss[idx]);
+                ben.csw_cnt = csw_cnts[idx];
+                ben.smp_cnt = smp_cnts[idx];
+                ben.rus_smrys = mem::take(&mut rus_valsss[idx])
+                    .into_iter()
+                    .map(|x| Arc::new(Smry::new(x, exc_otl)))
+                    .collect();
+                let bse = bses[idx].unwrap_or_default();
+                let smp_cnt = smp_cnts[idx] as u64;
+                ben.rus_sums =
+                    std::array::from_fn(|n| rus_sums[idx][n].saturating_sub(bse[n] * smp_cnt));
+                // Flag a batch median within timer noise, which suggests
+                // the benchmark was optimized away.
+                ben.nse = ben.smry.mdn * bat <= ovh.nse.max(1);
+                if ben_bld.drp == Drp::Sep {
+                    ben.drp_smry = Some(Arc::new(Smry::new(drp_vals, exc_otl)));
+                }
+                if let Err(e) = tx.send((ben_bld.reg_id, ben)) {
+                    println!("send ben error: {:?}", e);
+                }
+            }
+            if let Some(cor) = cor {
+                cors.lock().unwrap().push(cor);
+            }
+        };
+        let grp_cnt = grps.len();
+        for (grp_idx, grp_ben_blds) in grps.into_iter().enumerate() {
+            // println!("grp_ben_blds:{}", grp_ben_blds.len());
+            let run_grp = run_grp.clone();
+            pool.execute(move || run_grp(grp_idx, grp_ben_blds));
+        }
+        // Run cold benchmarks on one worker while the other workers are idle.
+        if !cld_ben_blds.is_empty() {
+            pool.join();
+            pool.execute(move || run_grp(grp_cnt, cld_ben_blds));
         }
 
         // Create registrations with benchmark results.
@@ -137,45 +357,137 @@ where
         for (reg_id, ben) in rx.iter().take(ben_cnt) {
             let reg = regs.entry(reg_id).or_insert_with(|| {
                 let reg_bld = self.reg_blds.get(&reg_id).unwrap();
-                Reg::new(&reg_bld.lbls)
+                let mut reg = Reg::new(&reg_bld.lbls);
+                reg.spc = reg_bld.spc;
+                reg
             });
             reg.bens.push(ben);
         }
         // println!("    regs:{:?}", regs);
 
+        // Diagnose benchmarks with medians within overhead noise.
+        let mut nses: Vec<Nse<L>> = Vec::new();
+        for reg in regs.values() {
+            for ben in reg.bens.iter().filter(|x| x.nse) {
+                nses.push(Nse {
+                    lbls: reg.lbls.clone(),
+                    lbl: ben.lbl,
+                    mdn: ben.smry.mdn,
+                });
+            }
+        }
+        nses.sort_unstable_by(|a, b| (&a.lbls, a.lbl).cmp(&(&b.lbls, b.lbl)));
+
+        // Report context switch contamination of each benchmark when checked.
+        let mut pmts: Vec<Pmt<L>> = Vec::new();
+        if csw != Csw::Off {
+            for reg in regs.values() {
+                for ben in reg.bens.iter() {
+                    pmts.push(Pmt {
+                        lbls: reg.lbls.clone(),
+                        lbl: ben.lbl,
+                        cnt: ben.csw_cnt,
+                        smp_cnt: ben.smp_cnt,
+                        csw,
+                    });
+                }
+            }
+        }
+        pmts.sort_unstable_by(|a, b| (&a.lbls, a.lbl).cmp(&(&b.lbls, b.lbl)));
+
+        // Measure the timestamp counter rate for throughput,
+        // once a benchmark with declared counts is selected.
+        let mut hz: Option<f64> = None;
+
         // Create selections from benchmark results.
+        // Outlier warnings are collected once for each selected benchmark metric.
+        let mut otl_wrns: Vec<OtlWrn<L>> = Vec::new();
         let mut sels = HashMap::with_capacity(qry_bld.sel_blds.len());
         for sel_bld in qry_bld.sel_blds.values() {
             // Get matching registration and raw benchmark results.
             let reg = regs.get(&sel_bld.reg_id()).unwrap();
 
+            // Per-sample CPU time medians are mostly zero at tick granularity.
+            if sel_bld.sta == Mdn && matches!(sel_bld.mtr, Mtr::Usr | Mtr::Sys) {
+                bail!(
+                    "median {} selection of registration '{}': per-sample cpu times are mostly zero; select Avg instead",
+                    sel_bld.mtr,
+                    join(&sel_bld.lbls, ',')
+                );
+            }
+
             // Apply a statistical function to each benchmark result.
+            // Multiple selections share the benchmark summary.
             let mut sta_vals = Vec::with_capacity(reg.bens.len());
             for ben in reg.bens.iter() {
-                // Clone benchmark values when necessary.
-                // Multiple selections may rely on the same benchmark values.
-                let val = match sel_bld.sta {
-                    Mdn => {
-                        let mdl = ben.vals.len() / 2;
-                        *ben.vals.clone().select_nth_unstable(mdl).1
-                    }
-                    Avg => {
-                        let len = ben.vals.len() as u64;
-                        ben.vals.iter().sum::<u64>().saturating_div(len)
+                // Select the summary of the selection metric.
+                let Some(smry) = ben.mtr_smry(sel_bld.mtr) else {
+                    bail!(
+                        "missing {} metric: benchmark '{}' of registration '{}' doesn't measure it",
+                        sel_bld.mtr,
+                        ben.lbl,
+                        join(&sel_bld.lbls, ',')
+                    );
+                };
+                if smry.otls.wrn()
+                    && !otl_wrns
+                        .iter()
+                        .any(|x| x.lbls == reg.lbls && x.lbl == ben.lbl && x.mtr == sel_bld.mtr)
+                {
+                    otl_wrns.push(OtlWrn {
+                        lbls: reg.lbls.clone(),
+                        lbl: ben.lbl,
+                        mtr: sel_bld.mtr,
+                        smry: smry.clone(),
+                    });
+                }
+                // Average CPU times are means over samples run, as tables report.
+                let val = match (sel_bld.mtr.rus_idx(), ben.rus_vals()) {
+                    (Some(idx), Some(rus))
+                        if matches!(sel_bld.mtr, Mtr::Usr | Mtr::Sys) && sel_bld.sta == Avg =>
+                    {
+                        rus[idx]
                     }
-                    Min => *ben.vals.iter().min().unwrap(),
-                    Max => *ben.vals.iter().max().unwrap(),
+                    _ => smry.sta(sel_bld.sta),
                 };
-                sta_vals.push(StaVal::new(ben.lbl, val));
+                let mut sta_val = StaVal::new(ben.lbl, val, smry.clone());
+                sta_val.gen = ben.gen;
+                sta_val.byt = ben.cnts.byt;
+                sta_val.rus = ben.rus_vals();
+                if sel_bld.mtr == Mtr::Cyc && ben.cnts != Cnts::default() {
+                    let hz = *hz.get_or_insert_with(tsc_hz);
+                    sta_val.tpt = Tpt::new(val, ben.cnts, hz);
+                }
+
+                // Bootstrap a confidence interval with a reproducible seed.
+                if let Some(bts) = qry_bld.bts.as_ref() {
+                    let mut rng = Rng::new_mix(bts.sed, (sel_bld.id(), ben.lbl));
+                    sta_val.ci = Some(bts.ci_sta(smry, sel_bld.sta, &mut rng));
+                }
+                sta_vals.push(sta_val);
             }
 
             // Sort vals based on lbl.
             sta_vals.sort_unstable_by_key(|x| x.lbl);
 
+            // Validate warm and cold samples aren't mixed.
+            let cld = reg.bens[0].cld;
+            if reg.bens.iter().any(|x| x.cld != cld) {
+                bail!(
+                    "mixed cache modes: benchmarks of selection '{}' run with different cold settings",
+                    join(&sel_bld.lbls, ',')
+                );
+            }
+
             // Store selection.
-            let sel = Sel::new(&sel_bld.lbls, sel_bld.sta, sta_vals);
+            let mut sel = Sel::new(&sel_bld.lbls, sel_bld.sta, sta_vals);
+            sel.mtr = sel_bld.mtr;
+            sel.spc = reg.spc;
+            sel.cld = cld;
+            sel.gen = SelGen::new(reg.bens.iter().map(|x| x.gen));
             sels.entry(sel_bld.id()).or_insert(sel);
         }
+        otl_wrns.sort_unstable_by(|a, b| (&a.lbls, a.lbl, a.mtr).cmp(&(&b.lbls, b.lbl, b.mtr)));
         // println!("    sels:{:?}", sels);
 
         // Create comparisons.
@@ -190,50 +502,108 @@ where
                 Some(x) => x,
             };
 
-            // Validate that labels have equal lengths.
-            if a_sel.vals.len() != b_sel.vals.len() {
-                bail!(
-                    "uneven selection lengths: (a len:{}, b len:{})",
-                    a_sel.vals.len(),
-                    b_sel.vals.len()
-                )
-            }
-            // Validate each label by index.
-            // Values were previously sorted by label.
-            for (idx, (a, b)) in a_sel.vals.iter().zip(b_sel.vals.iter()).enumerate() {
-                if a.lbl != b.lbl {
-                    bail!("unequal labels: idx:{} (a:{}, b:{})", idx, a.lbl, b.lbl)
+            // Store the comparison.
+            let cmp = Cmp::new(a_sel, b_sel, &qry_bld, cch.as_ref())?;
+            cmps.push(cmp);
+        }
+        // println!("    cmps:{:?}", cmps);
+
+        // Create multi-selection comparisons.
+        let mut mlts = Vec::with_capacity(qry_bld.mlt_blds.len());
+        for mlt_bld in qry_bld.mlt_blds.iter() {
+            let mut mlt_sels: Vec<&Sel<L>> = Vec::with_capacity(mlt_bld.sel_ids.len());
+            for sel_id in mlt_bld.sel_ids.iter() {
+                match sels.get(sel_id) {
+                    None => bail!("missing sel: sel_id {}", sel_id),
+                    Some(x) => mlt_sels.push(x),
                 }
             }
+            let ref_idx = match mlt_bld
+                .sel_ids
+                .iter()
+                .position(|x| *x == mlt_bld.ref_sel_id)
+            {
+                None => bail!("missing sel: ref_sel_id {}", mlt_bld.ref_sel_id),
+                Some(x) => x,
+            };
 
-            // Create comparison data.
-            let hdr_lbls: Vec<L> = a_sel.vals.iter().map(|x| x.lbl).collect();
-            let a_lbls: Vec<L> = a_sel.lbls.clone();
-            let b_lbls: Vec<L> = b_sel.lbls.clone();
-            let a_vals: Vec<u64> = a_sel.vals.iter().map(|x| x.val).collect();
-            let b_vals: Vec<u64> = b_sel.vals.iter().map(|x| x.val).collect();
+            // Store the comparison.
+            let mlt = Mlt::new(&mlt_sels, ref_idx, &qry_bld)?;
+            mlts.push(mlt);
+        }
 
-            // Calculate the ratio of values at each index.
-            let mut ratios: Vec<f32> = Vec::with_capacity(a_vals.len());
-            for (a, b) in a_vals.iter().zip(b_vals.iter()) {
-                let a = *a as f32;
-                let b = *b as f32;
-                let (mut min, max) = if a < b { (a, b) } else { (b, a) };
-                min = min.max(1.0);
-                let ratio = f32_pnt_one(max / min);
-                ratios.push(ratio);
+        // Create normalized series.
+        let mut nrms = Vec::with_capacity(qry_bld.nrm_blds.len());
+        for nrm_bld in qry_bld.nrm_blds.iter() {
+            let ref_sel = match sels.get(&nrm_bld.ref_sel_id) {
+                None => bail!("missing sel: ref_sel_id {}", nrm_bld.ref_sel_id),
+                Some(x) => x,
+            };
+            let mut nrm_sels: Vec<&Sel<L>> = Vec::with_capacity(nrm_bld.sel_ids.len());
+            for sel_id in nrm_bld.sel_ids.iter() {
+                match sels.get(sel_id) {
+                    None => bail!("missing sel: sel_id {}", sel_id),
+                    Some(x) => nrm_sels.push(x),
+                }
             }
 
-            // Store the comparison.
-            let cmp = Cmp::new(hdr_lbls, a_lbls, b_lbls, a_vals, b_vals, ratios);
-            cmps.push(cmp);
+            // Store the normalized series.
+            let nrm = Nrm::new(ref_sel, &nrm_sels, &qry_bld)?;
+            nrms.push(nrm);
+        }
+
+        // Create complexity fits.
+        let mut fits = Vec::with_capacity(qry_bld.fit_blds.len());
+        for fit_bld in qry_bld.fit_blds.iter() {
+            let sel = match sels.get(&fit_bld.sel_id) {
+                None => bail!("missing sel: sel_id {}", fit_bld.sel_id),
+                Some(x) => x,
+            };
+            fits.extend(Fit::new(sel));
+        }
+
+        // Create roofline comparisons.
+        // Machine peaks are calibrated once, after benchmarks finish.
+        let mut rfls = Vec::with_capacity(qry_bld.rfl_blds.len());
+        if !qry_bld.rfl_blds.is_empty() {
+            let pek = Pek::cal();
+            for rfl_bld in qry_bld.rfl_blds.iter() {
+                let mut rfl_sels: Vec<&Sel<L>> = Vec::with_capacity(rfl_bld.sel_ids.len());
+                for sel_id in rfl_bld.sel_ids.iter() {
+                    match sels.get(sel_id) {
+                        None => bail!("missing sel: sel_id {}", sel_id),
+                        Some(x) => rfl_sels.push(x),
+                    }
+                }
+                rfls.push(Rfl::new(&rfl_sels, pek, &qry_bld)?);
+            }
         }
-        // println!("    cmps:{:?}", cmps);
 
         // Print comparisons.
-        for cmp in cmps {
+        for cmp in cmps.iter() {
             println!("{}", cmp);
         }
+        for mlt in mlts.iter() {
+            println!("{}", mlt);
+        }
+        for nrm in nrms.iter() {
+            println!("{}", nrm);
+        }
+        for fit in fits.iter() {
+            println!("{}", fit);
+        }
+        for rfl in rfls.iter() {
+            println!("{}", rfl);
+        }
+        for otl_wrn in otl_wrns.iter() {
+            println!("{}", otl_wrn);
+        }
+        for nse in nses.iter() {
+            println!("{}", nse);
+        }
+        for pmt in pmts.iter().filter(|x| x.cnt != 0) {
+            println!("{}", pmt);
+        }
 
         // for ben in sel.bens.iter() {
         //     // Merge labels.
@@ -244,23 +614,53 @@ where
         //     println!("    ellapsed:{:?}", ellapsed);
         // }
 
-        Ok(qry)
+        Ok(Qry {
+            sels,
+            cmps,
+            mlts,
+            nrms,
+            fits,
+            rfls,
+            otl_wrns,
+            nses,
+            hz,
+            cch,
+            sch,
+            env,
+            pmts,
+        })
     }
 }
+/// A registration builder function inserting benchmark functions.
+pub type RegFn<L> = Box<dyn FnMut(&mut RegBld<L>)>;
+
 pub struct RegBld<L>
 where
     L: Label,
 {
     pub id: u64,
     pub lbls: Vec<L>,
-    pub f: fn(&mut RegBld<L>),
+    pub f: Option<RegFn<L>>,
     pub ben_blds: Vec<BenBld<L>>,
+    /// Throughput declared for subsequently inserted benchmarks.
+    pub thr: Thr,
+    /// Drop timing of subsequently inserted benchmarks.
+    pub drp: Drp,
+    /// Calls timed together in each sample of subsequently inserted benchmarks.
+    pub bat: u32,
+    /// Spacing of struct label values, recorded by sweeps.
+    pub spc: Spc,
+    /// Cache and branch predictor state of subsequently inserted benchmarks.
+    pub cld: Cld,
 }
 impl<L> RegBld<L>
 where
     L: Label,
 {
-    pub fn new(lbls: &[L], f: fn(&mut RegBld<L>)) -> Self {
+    pub fn new<F>(lbls: &[L], f: F) -> Self
+    where
+        F: FnMut(&mut RegBld<L>) + 'static,
+    {
         // Unique and sort the labels for consistent hash id.
         let unq_srt_lbls = unq_srt(lbls);
         // Create a hash id from the labels.
@@ -271,63 +671,362 @@ where
         RegBld {
             id: h.finish(),
             lbls: unq_srt_lbls,
-            f,
+            f: Some(Box::new(f)),
             ben_blds: Vec::new(),
+            thr: Thr::default(),
+            drp: Drp::default(),
+            bat: 1,
+            spc: Spc::default(),
+            cld: Cld::default(),
         }
     }
     #[inline]
     pub fn ins_ben_blds(&mut self) {
-        (self.f)(self);
+        self.thr = Thr::default();
+        self.drp = Drp::default();
+        self.bat = 1;
+        self.spc = Spc::default();
+        self.cld = Cld::default();
+        // Take the builder while it borrows the registration.
+        if let Some(mut f) = self.f.take() {
+            f(self);
+            self.f = Some(f);
+        }
+    }
+    /// Declares elements processed per call by subsequently inserted benchmarks.
+    pub fn elm(&mut self, cnt: Cnt) -> &mut Self {
+        self.thr.elm = Some(cnt);
+        self
+    }
+    /// Declares bytes processed per call by subsequently inserted benchmarks.
+    pub fn byt(&mut self, cnt: Cnt) -> &mut Self {
+        self.thr.byt = Some(cnt);
+        self
+    }
+    /// Sets drop timing of values returned by subsequently inserted benchmarks.
+    pub fn drp(&mut self, drp: Drp) -> &mut Self {
+        self.drp = drp;
+        self
+    }
+    /// Declares arithmetic operations per call by subsequently inserted benchmarks.
+    pub fn ops(&mut self, cnt: Cnt) -> &mut Self {
+        self.thr.ops = Some(cnt);
+        self
+    }
+    /// Sets the count of calls timed together in each sample.
+    ///
+    /// Applies to subsequently inserted benchmarks, except manually timed benchmarks.
+    /// Each sample is the batch's ellapsed cycles divided by the batch count,
+    /// which amortizes timestamp overhead and, with setup, large setups.
+    pub fn bat(&mut self, bat: u32) -> &mut Self {
+        self.bat = bat.max(1);
+        self
+    }
+    /// Sets cache and branch predictor state before each sample.
+    ///
+    /// Applies to subsequently inserted benchmarks.
+    /// Cold preparation runs after setup and outside the timed region.
+    /// Streaming eviction reads a buffer twice the last-level cache size.
+    /// Cold benchmarks run on one worker after warm benchmarks finish.
+    pub fn cld(&mut self, cld: Cld) -> &mut Self {
+        self.cld = cld;
+        self
     }
     /// Insert a benchmark function.
-    pub fn ins<O>(&mut self, lbl: L, f: fn() -> O) -> &mut Self {
-        // Capture a function pointer to the benchmark function.
-        // Enables the benchmark function to return a genericaly typed value
-        // while the benchmark returns a u64 timestamp value.
+    ///
+    /// Accepts function pointers and `Send` closures, which may capture
+    /// owned runtime data such as lengths, inputs or shared state.
+    pub fn ins<F, O>(&mut self, lbl: L, mut f: F) -> &mut Self
+    where
+        F: FnMut() -> O + Send + 'static,
+        O: 'static,
+    {
         // Returning a value from the benchmark function, in coordination with `black_box()`,
         // disallows the compiler from optimizing away inner logic.
-        // Returns `fn() -> u64` to enable selecting and running benchmark functions.
-        let fn_ptr = unsafe { OpaqueFnPtr::from_fn(f) };
-
-        #[inline]
-        fn ben<O>(fn_ptr: OpaqueFnPtr) -> u64 {
-            let ben: fn() -> O = unsafe { fn_ptr.to_fn() };
-            // Avoid compiler over-optimization of benchmark functions by using `black_box(f())`.
-            //  Explanation of how black_box works with LLVM ASM and memory.
-            //      https://github.com/rust-lang/rust/blob/6a944187fb917393c9c6c39825dec3c1de29787c/compiler/rustc_codegen_llvm/src/intrinsic.rs#L339
-            // `black_box` call from rust benchmark.
-            //      https://github.com/rust-lang/rust/blob/cb6ab9516bbbd3859b56dd23e32fe41600e0ae02/library/test/src/lib.rs#L628
-            // Record cpu cycles with assembly instructions.
-            // Return ellapsed cpu cycles.
-            let fst = fst_cpu_cyc();
-            black_box(ben());
-            lst_cpu_cyc() - fst
+        // Avoid compiler over-optimization of benchmark functions by using `black_box(f())`.
+        //  Explanation of how black_box works with LLVM ASM and memory.
+        //      https://github.com/rust-lang/rust/blob/6a944187fb917393c9c6c39825dec3c1de29787c/compiler/rustc_codegen_llvm/src/intrinsic.rs#L339
+        // `black_box` call from rust benchmark.
+        //      https://github.com/rust-lang/rust/blob/cb6ab9516bbbd3859b56dd23e32fe41600e0ae02/library/test/src/lib.rs#L628
+        let ben = move |drp: Drp, bat: u32, smp: &mut Smp| tme_bat(drp, bat, smp, |_| f());
+        self.ins_ben_bld(lbl, Box::new(ben))
+    }
+    /// Insert a benchmark function which receives a copied input.
+    ///
+    /// The input passes through `black_box()` before every call,
+    /// which prevents the compiler from constant folding the input.
+    pub fn ins_inp<I, F, O>(&mut self, lbl: L, inp: I, mut f: F) -> &mut Self
+    where
+        I: Copy + Send + 'static,
+        F: FnMut(I) -> O + Send + 'static,
+        O: 'static,
+    {
+        self.ins(lbl, move || f(black_box(inp)))
+    }
+    /// Insert a benchmark function which receives a reference to an input.
+    ///
+    /// The input reference passes through `black_box()` before every call.
+    pub fn ins_inp_ref<I, F, O>(&mut self, lbl: L, inp: I, mut f: F) -> &mut Self
+    where
+        I: Send + 'static,
+        F: FnMut(&I) -> O + Send + 'static,
+        O: 'static,
+    {
+        self.ins(lbl, move || f(black_box(&inp)))
+    }
+    /// Insert a benchmark function which receives a flushed input slice.
+    ///
+    /// The input is registered as a region flushed from every cache level
+    /// with `clflush` before each sample.
+    pub fn ins_fls<T, F, O>(&mut self, lbl: L, inp: Vec<T>, mut f: F) -> &mut Self
+    where
+        T: Send + 'static,
+        F: FnMut(&[T]) -> O + Send + 'static,
+        O: 'static,
+    {
+        // Moving the vector into the benchmark keeps its heap region in place.
+        let fls = (inp.as_ptr() as usize, mem::size_of_val(inp.as_slice()));
+        self.ins(lbl, move || f(black_box(&inp)));
+        if let Some(ben_bld) = self.ben_blds.last_mut() {
+            ben_bld.cld.evc = Evc::Fls;
+            ben_bld.fls = Some(fls);
+        }
+        self
+    }
+    /// Insert a benchmark function which takes generated input data by mutable reference.
+    ///
+    /// Data is generated once, and copied for each call outside the timed region.
+    /// The generator is recorded with the benchmark results.
+    pub fn ins_gen<T, F, O>(&mut self, lbl: L, gen: Gen, len: usize, f: F) -> &mut Self
+    where
+        T: GenVal,
+        F: FnMut(&mut Vec<T>) -> O + Send + 'static,
+        O: 'static,
+    {
+        let inp: Vec<T> = gen.gen(len);
+        self.ins_setup_ref(lbl, move || inp.clone(), f);
+        if let Some(ben_bld) = self.ben_blds.last_mut() {
+            ben_bld.gen = Some(gen);
+        }
+        self
+    }
+    /// Insert a benchmark function for each input pattern of a generator seed.
+    ///
+    /// `lbl` constructs a label from each pattern, e.g. `Inp`,
+    /// which makes the pattern a label dimension for selections.
+    pub fn ins_gens<C, T, F, O>(
+        &mut self,
+        lbl: C,
+        pats: &[Pat],
+        sed: u64,
+        len: usize,
+        f: F,
+    ) -> &mut Self
+    where
+        C: Fn(Pat) -> L,
+        T: GenVal,
+        F: FnMut(&mut Vec<T>) -> O + Clone + Send + 'static,
+        O: 'static,
+    {
+        for pat in pats.iter() {
+            self.ins_gen(lbl(*pat), Gen::new(*pat, sed), len, f.clone());
         }
-
-        self.ben_blds
-            .push(BenBld::new(self.id, lbl, fn_ptr, ben::<O>));
         self
     }
     /// Insert a benchmark function which is manually timed.
     ///
     /// The caller is expected to call `start()` and `stop()` functions
     /// on the specified `Tme` parameter.
-    pub fn ins_prm<O>(&mut self, lbl: L, f: fn(Rc<RefCell<Tme>>) -> O) -> &mut Self {
-        let fn_ptr = unsafe { OpaqueFnPtr::from_fn(f) };
-
-        #[inline]
-        fn ben<O>(fn_ptr: OpaqueFnPtr) -> u64 {
-            let ben: fn(Rc<RefCell<Tme>>) -> O = unsafe { fn_ptr.to_fn() };
+    pub fn ins_prm<F, O>(&mut self, lbl: L, mut f: F) -> &mut Self
+    where
+        F: FnMut(Rc<RefCell<Tme>>) -> O + Send + 'static,
+        O: 'static,
+    {
+        let ben = move |drp: Drp, _: u32, smp: &mut Smp| {
+            smp.bgn();
             let tme = Rc::new(RefCell::new(Tme(0)));
-            black_box(ben(tme.clone()));
+            // The return value drops after the manual stop timestamp.
+            let o = black_box(f(tme.clone()));
+            smp.end();
+            let drp_cyc = match drp {
+                Drp::Sep => {
+                    let fst = fst_cpu_cyc();
+                    drop(o);
+                    lst_cpu_cyc() - fst
+                }
+                _ => 0,
+            };
             let x = tme.borrow();
-            x.0
+            (x.0, drp_cyc)
+        };
+        self.ins_ben_bld(lbl, Box::new(ben));
+        // Manually timed benchmarks aren't batched.
+        if let Some(ben_bld) = self.ben_blds.last_mut() {
+            ben_bld.bat = 1;
         }
-
-        self.ben_blds
-            .push(BenBld::new(self.id, lbl, fn_ptr, ben::<O>));
         self
     }
+    /// Insert a benchmark function which consumes an input.
+    ///
+    /// `setup` produces an input for each call outside the timed region.
+    pub fn ins_setup<S, F, I, O>(&mut self, lbl: L, mut setup: S, mut f: F) -> &mut Self
+    where
+        S: FnMut() -> I + Send + 'static,
+        F: FnMut(I) -> O + Send + 'static,
+        I: 'static,
+        O: 'static,
+    {
+        let ben = move |drp: Drp, bat: u32, smp: &mut Smp| {
+            let mut inps = (0..bat).map(|_| setup()).collect::<Vec<I>>().into_iter();
+            tme_bat(drp, bat, smp, |_| f(inps.next().unwrap()))
+        };
+        self.ins_ben_bld(lbl, Box::new(ben))
+    }
+    /// Insert a benchmark function which takes a mutable reference to an input.
+    ///
+    /// `setup` produces an input for each call outside the timed region.
+    pub fn ins_setup_ref<S, F, I, O>(&mut self, lbl: L, setup: S, f: F) -> &mut Self
+    where
+        S: FnMut() -> I + Send + 'static,
+        F: FnMut(&mut I) -> O + Send + 'static,
+        I: 'static,
+        O: 'static,
+    {
+        self.ins_setup_teardown(lbl, setup, f, drop)
+    }
+    /// Insert a benchmark function which takes a mutable reference to an input.
+    ///
+    /// `setup` produces an input for each call, and `teardown` consumes
+    /// each input, outside the timed region.
+    pub fn ins_setup_teardown<S, F, T, I, O>(
+        &mut self,
+        lbl: L,
+        mut setup: S,
+        mut f: F,
+        mut teardown: T,
+    ) -> &mut Self
+    where
+        S: FnMut() -> I + Send + 'static,
+        F: FnMut(&mut I) -> O + Send + 'static,
+        T: FnMut(I) + Send + 'static,
+        I: 'static,
+        O: 'static,
+    {
+        let ben = move |drp: Drp, bat: u32, smp: &mut Smp| {
+            let mut inps: Vec<I> = (0..bat).map(|_| setup()).collect();
+            let ret = tme_bat(drp, bat, smp, |n| f(&mut inps[n]));
+            inps.into_iter().for_each(&mut teardown);
+            ret
+        };
+        self.ins_ben_bld(lbl, Box::new(ben))
+    }
+    /// Sets the spacing of struct label values for manually inserted series.
+    pub fn spc(&mut self, spc: Spc) -> &mut Self {
+        self.spc = spc;
+        self
+    }
+    /// Insert a benchmark function for each value of a parameter sweep.
+    ///
+    /// `lbl` constructs a struct label from each value, e.g. `Len`.
+    /// The benchmark function receives the value through `black_box()`,
+    /// and the sweep spacing is recorded for the registration.
+    pub fn swp<C, F, O>(&mut self, lbl: C, swp: Swp, f: F) -> &mut Self
+    where
+        C: Fn(u32) -> L,
+        F: FnMut(u32) -> O + Clone + Send + 'static,
+        O: 'static,
+    {
+        for val in swp.vals() {
+            let mut f = f.clone();
+            self.ins(lbl(val), move || f(black_box(val)));
+        }
+        self.spc = swp.spc();
+        self
+    }
+    /// Insert a benchmark function for a type parameter, e.g. from `ins_typs!`.
+    ///
+    /// Bytes per call are derived from declared elements and the type size,
+    /// unless bytes are declared explicitly.
+    pub fn ins_typ<T, F, O>(&mut self, lbl: L, f: F) -> &mut Self
+    where
+        F: FnMut() -> O + Send + 'static,
+        O: 'static,
+    {
+        let thr = self.thr;
+        if thr.elm.is_some() && thr.byt.is_none() {
+            self.thr.byt = Some(Cnt::Elm(mem::size_of::<T>() as u64));
+        }
+        self.ins(lbl, f);
+        self.thr = thr;
+        self
+    }
+    /// Insert a benchmark with the current throughput, drop, batch and cold settings.
+    fn ins_ben_bld(&mut self, lbl: L, f: BenFn) -> &mut Self {
+        let mut ben_bld = BenBld::new(self.id, lbl, f, self.thr, self.drp, self.bat);
+        ben_bld.cld = self.cld;
+        self.ben_blds.push(ben_bld);
+        self
+    }
+}
+
+/// Times a batch of benchmark calls.
+///
+/// Returns ellapsed cycles, and drop cycles when measured separately,
+/// for the whole batch.
+#[inline]
+fn tme_bat<O>(drp: Drp, bat: u32, smp: &mut Smp, mut f: impl FnMut(usize) -> O) -> (u64, u64) {
+    // Prepare the sample before the start timestamp.
+    smp.bgn();
+
+    // Record cpu cycles with assembly instructions.
+    match drp {
+        Drp::Inc => {
+            // Temporary return values drop before the stop timestamp.
+            let fst = fst_cpu_cyc();
+            for n in 0..bat as usize {
+                black_box(f(n));
+            }
+            let cyc = lst_cpu_cyc() - fst;
+            smp.end();
+            (cyc, 0)
+        }
+        Drp::Exc | Drp::Sep if bat == 1 => {
+            let fst = fst_cpu_cyc();
+            let o = black_box(f(0));
+            let cyc = lst_cpu_cyc() - fst;
+            smp.end();
+            // Time the drop only when measured separately.
+            let drp_cyc = match drp {
+                Drp::Sep => {
+                    let fst = fst_cpu_cyc();
+                    drop(o);
+                    lst_cpu_cyc() - fst
+                }
+                _ => 0,
+            };
+            (cyc, drp_cyc)
+        }
+        Drp::Exc | Drp::Sep => {
+            // Hold return values until after the stop timestamp.
+            let mut os: Vec<O> = Vec::with_capacity(bat as usize);
+            let fst = fst_cpu_cyc();
+            for n in 0..bat as usize {
+                os.push(black_box(f(n)));
+            }
+            let cyc = lst_cpu_cyc() - fst;
+            smp.end();
+            // Time the drop only when measured separately.
+            let drp_cyc = match drp {
+                Drp::Sep => {
+                    let fst = fst_cpu_cyc();
+                    drop(os);
+                    lst_cpu_cyc() - fst
+                }
+                _ => 0,
+            };
+            (cyc, drp_cyc)
+        }
+    }
 }
 impl<L> fmt::Debug for RegBld<L>
 where
@@ -343,6 +1042,28 @@ where
 {
     pub sel_blds: HashMap<u64, SelBld<L>>,
     pub cmp_blds: Vec<CmpBld>,
+    pub mlt_blds: Vec<MltBld>,
+    pub nrm_blds: Vec<NrmBld>,
+    pub fit_blds: Vec<FitBld>,
+    pub rfl_blds: Vec<RflBld>,
+    /// Whether outliers are excluded before applying statistical functions.
+    pub exc_otl: bool,
+    /// Bootstrap settings for confidence intervals.
+    pub bts: Option<Bts>,
+    /// Significance level for comparison tests.
+    pub alp: f64,
+    /// Whether comparison tables highlight winners with colour.
+    pub clr: bool,
+    /// Alignment of selection values in comparisons.
+    pub aln: Aln,
+    /// Order of samples within each worker.
+    pub sch: Sch,
+    /// Whether failed environment checks refuse to run.
+    pub stc: bool,
+    /// Context switch checks of each sample.
+    pub csw: Csw,
+    /// Whether comparison tables show resource usage rows.
+    pub rus: bool,
 }
 impl<L> QryBld<L>
 where
@@ -352,13 +1073,104 @@ where
         QryBld {
             sel_blds: HashMap::new(),
             cmp_blds: Vec::new(),
+            mlt_blds: Vec::new(),
+            nrm_blds: Vec::new(),
+            fit_blds: Vec::new(),
+            rfl_blds: Vec::new(),
+            exc_otl: false,
+            bts: None,
+            alp: ALP,
+            clr: false,
+            aln: Aln::default(),
+            sch: Sch::default(),
+            stc: false,
+            csw: Csw::default(),
+            rus: false,
         }
     }
+    /// Excludes outliers from benchmark values before
+    /// statistical functions are applied.
+    ///
+    /// Outliers are classified as mild or severe with
+    /// Tukey fences and MAD-based z-scores.
+    pub fn exc_otl(&mut self) -> &mut Self {
+        self.exc_otl = true;
+        self
+    }
+    /// Calculates bootstrap confidence intervals for statistical values and ratios.
+    ///
+    /// `cnt` is the count of resamples, `cnf` is the confidence level, e.g. `0.95`,
+    /// and `sed` seeds resampling for reproducible results.
+    pub fn bts(&mut self, cnt: u32, cnf: f64, sed: u64) -> &mut Self {
+        self.bts = Some(Bts::new(cnt, cnf, sed));
+        self
+    }
+    /// Sets the significance level of comparison tests.
+    ///
+    /// Defaults to `0.05`.
+    pub fn alp(&mut self, alp: f64) -> &mut Self {
+        self.alp = alp;
+        self
+    }
+    /// Highlights comparison winners with colour in terminal tables.
+    pub fn clr(&mut self) -> &mut Self {
+        self.clr = true;
+        self
+    }
+    /// Sets how comparisons align selection values by label.
+    ///
+    /// Defaults to strict alignment, which requires identical labels.
+    pub fn aln(&mut self, aln: Aln) -> &mut Self {
+        self.aln = aln;
+        self
+    }
+    /// Sets the order of samples within each worker.
+    ///
+    /// Interleaved schedules run benchmarks of the same column in one worker,
+    /// which spreads frequency, thermal and background drift across them.
+    /// Cold benchmarks run in a separate phase on one worker, after warm benchmarks
+    /// finish, and the schedule orders their samples within that phase.
+    /// Defaults to sequential samples of each benchmark.
+    pub fn sch(&mut self, sch: Sch) -> &mut Self {
+        self.sch = sch;
+        self
+    }
+    /// Refuses to run when environment checks fail.
+    ///
+    /// Checks otherwise print warnings before measuring.
+    pub fn stc(&mut self) -> &mut Self {
+        self.stc = true;
+        self
+    }
+    /// Checks each sample for context switches with `getrusage`.
+    ///
+    /// Counters are read before cold preparation and after the stop timestamp,
+    /// which excludes setup and teardown. Resource usage of preparation alone
+    /// is subtracted as a no-work baseline.
+    /// Preempted samples are flagged or discarded, and contamination rates
+    /// are reported for each benchmark.
+    pub fn csw(&mut self, csw: Csw) -> &mut Self {
+        self.csw = csw;
+        self
+    }
+    /// Shows resource usage rows in comparison tables.
+    ///
+    /// Rows of median page faults and peak resident set size growth,
+    /// and mean user and system CPU time, are shown when any value is non-zero.
+    pub fn rus(&mut self) -> &mut Self {
+        self.rus = true;
+        self
+    }
     pub fn sel(&mut self, lbls: &[L]) -> u64 {
         self.sel_sta(lbls, Mdn)
     }
     pub fn sel_sta(&mut self, lbls: &[L], sta: Sta) -> u64 {
-        let sel = SelBld::new(lbls, sta);
+        self.sel_mtr(lbls, sta, Mtr::Cyc)
+    }
+    /// Selects a metric of benchmark results, e.g. drop cycles or page faults.
+    pub fn sel_mtr(&mut self, lbls: &[L], sta: Sta, mtr: Mtr) -> u64 {
+        let mut sel = SelBld::new(lbls, sta);
+        sel.mtr = mtr;
         let sel_id = sel.id();
         self.sel_blds.entry(sel_id).or_insert(sel);
         sel_id
@@ -367,6 +1179,36 @@ where
         let cmp = CmpBld::new(a_sel_id, b_sel_id);
         self.cmp_blds.push(cmp);
     }
+    /// Compares multiple selections in a single table.
+    ///
+    /// Each selection is compared to the reference selection `ref_sel_id`,
+    /// which is expected to be one of `sel_ids`.
+    pub fn cmp_mlt(&mut self, sel_ids: &[u64], ref_sel_id: u64) {
+        let mlt = MltBld::new(sel_ids, ref_sel_id);
+        self.mlt_blds.push(mlt);
+    }
+    /// Normalizes selections to a reference selection.
+    ///
+    /// Values of each selection are shown as multiples of the
+    /// reference selection, which is normalized to one.
+    pub fn nrm(&mut self, ref_sel_id: u64, sel_ids: &[u64]) {
+        let nrm = NrmBld::new(ref_sel_id, sel_ids);
+        self.nrm_blds.push(nrm);
+    }
+    /// Fits a selection's series against asymptotic complexity models.
+    ///
+    /// The selection is expected to have struct labels, e.g. `Len(u32)`.
+    pub fn fit(&mut self, sel_id: u64) {
+        let fit = FitBld::new(sel_id);
+        self.fit_blds.push(fit);
+    }
+    /// Compares selections against calibrated machine peaks.
+    ///
+    /// Benchmarks are expected to declare bytes and operations with `RegBld`.
+    pub fn rfl(&mut self, sel_ids: &[u64]) {
+        let rfl = RflBld::new(sel_ids);
+        self.rfl_blds.push(rfl);
+    }
 }
 impl<L> fmt::Debug for QryBld<L>
 where
@@ -376,6 +1218,19 @@ where
         f.debug_struct("QryBld")
             .field("sel_blds", &self.sel_blds.values())
             .field("cmp_blds", &self.cmp_blds)
+            .field("mlt_blds", &self.mlt_blds)
+            .field("nrm_blds", &self.nrm_blds)
+            .field("fit_blds", &self.fit_blds)
+            .field("rfl_blds", &self.rfl_blds)
+            .field("exc_otl", &self.exc_otl)
+            .field("bts", &self.bts)
+            .field("alp", &self.alp)
+            .field("clr", &self.clr)
+            .field("aln", &self.aln)
+            .field("sch", &self.sch)
+            .field("stc", &self.stc)
+            .field("csw", &self.csw)
+            .field("rus", &self.rus)
             .finish()
     }
 }
@@ -386,6 +1241,7 @@ where
 {
     pub lbls: Vec<L>,
     pub sta: Sta,
+    pub mtr: Mtr,
 }
 impl<L> SelBld<L>
 where
@@ -395,15 +1251,17 @@ where
         SelBld {
             lbls: unq_srt(lbls),
             sta,
+            mtr: Mtr::default(),
         }
     }
-    /// Hash id for selection `labels` and `statistic`.
+    /// Hash id for selection `labels`, `statistic` and `metric`.
     pub fn id(&self) -> u64 {
         let mut h = DefaultHasher::new();
         for lbl in self.lbls.iter() {
             lbl.hash(&mut h);
         }
         self.sta.hash(&mut h);
+        self.mtr.hash(&mut h);
         h.finish()
     }
     /// Hash id for selection `labels`.
@@ -425,30 +1283,168 @@ impl CmpBld {
         CmpBld { a_sel_id, b_sel_id }
     }
 }
+#[derive(Debug)]
+pub struct MltBld {
+    pub sel_ids: Vec<u64>,
+    pub ref_sel_id: u64,
+}
+impl MltBld {
+    pub fn new(sel_ids: &[u64], ref_sel_id: u64) -> Self {
+        MltBld {
+            sel_ids: sel_ids.to_vec(),
+            ref_sel_id,
+        }
+    }
+}
+#[derive(Debug)]
+pub struct NrmBld {
+    pub ref_sel_id: u64,
+    pub sel_ids: Vec<u64>,
+}
+impl NrmBld {
+    pub fn new(ref_sel_id: u64, sel_ids: &[u64]) -> Self {
+        NrmBld {
+            ref_sel_id,
+            sel_ids: sel_ids.to_vec(),
+        }
+    }
+}
+#[derive(Debug)]
+pub struct FitBld {
+    pub sel_id: u64,
+}
+impl FitBld {
+    pub fn new(sel_id: u64) -> Self {
+        FitBld { sel_id }
+    }
+}
+#[derive(Debug)]
+pub struct RflBld {
+    pub sel_ids: Vec<u64>,
+}
+impl RflBld {
+    pub fn new(sel_ids: &[u64]) -> Self {
+        RflBld {
+            sel_ids: sel_ids.to_vec(),
+        }
+    }
+}
+/// A benchmark function timing a batch of calls.
+///
+/// Takes drop timing, a batch count, and sample hooks run around timing.
+/// Returns ellapsed cycles, and drop cycles when measured separately.
+pub type BenFn = Box<dyn FnMut(Drp, u32, &mut Smp) -> (u64, u64) + Send>;
+
+/// Hooks run around the timed region of a sample.
+///
+/// Resource usage is read before sample preparation and after the stop timestamp,
+/// which excludes setup and teardown, and keeps the read away from the start timestamp.
+/// Manually timed benchmarks read around the whole call.
+/// Preparation is removed by subtracting a no-work baseline from `bse`.
+pub struct Smp<'a> {
+    /// Prepares the sample after setup, before the start timestamp.
+    pub prp: &'a mut dyn FnMut(),
+    /// Whether resource usage is read.
+    pub rd_rus: bool,
+    /// Resource usage before sample preparation.
+    pub fst: Rus,
+    /// Resource usage after the stop timestamp.
+    pub lst: Rus,
+}
+impl<'a> Smp<'a> {
+    pub fn new(prp: &'a mut dyn FnMut(), rd_rus: bool) -> Self {
+        Smp {
+            prp,
+            rd_rus,
+            fst: Rus::default(),
+            lst: Rus::default(),
+        }
+    }
+    /// Reads resource usage, and prepares the sample before the start timestamp.
+    #[inline]
+    pub fn bgn(&mut self) {
+        if self.rd_rus {
+            // Reset the peak resident set size, so the sample's peak growth is read.
+            // `Stdy::run` checks `getrusage` before running samples.
+            rst_hwm();
+            self.fst = Rus::thd().unwrap();
+            self.fst.hwm = rd_hwm().unwrap_or_default();
+        }
+        (self.prp)();
+    }
+    /// Reads resource usage after the stop timestamp.
+    #[inline]
+    pub fn end(&mut self) {
+        if self.rd_rus {
+            self.lst = Rus::thd().unwrap();
+            self.lst.hwm = rd_hwm().unwrap_or_default();
+        }
+    }
+    /// Returns the resource usage of preparation alone,
+    /// the mean of `BSE_CNT` no-work samples.
+    pub fn bse(&mut self) -> RusVals {
+        let mut bse = RusVals::default();
+        for _ in 0..BSE_CNT {
+            self.bgn();
+            self.end();
+            for (x, val) in bse.iter_mut().zip(self.lst.dlt(&self.fst)) {
+                *x += val;
+            }
+        }
+        bse.map(|x| x / BSE_CNT as u64)
+    }
+}
+
+/// Count of no-work samples of a resource usage baseline.
+pub const BSE_CNT: u32 = 8;
+
 pub struct BenBld<L>
 where
     L: Label,
 {
     pub reg_id: u64,
     pub lbl: L,
-    pub fn_ptr: OpaqueFnPtr,
-    pub f: fn(OpaqueFnPtr) -> u64,
+    pub f: BenFn,
+    pub thr: Thr,
+    pub drp: Drp,
+    pub bat: u32,
+    /// Input data generator, when inputs are generated.
+    pub gen: Option<Gen>,
+    /// Cache and branch predictor state before each sample.
+    pub cld: Cld,
+    /// Address and byte length of an input region flushed before each sample.
+    pub fls: Option<(usize, usize)>,
 }
 impl<L> BenBld<L>
 where
     L: Label,
 {
-    pub fn new(reg_id: u64, lbl: L, fn_ptr: OpaqueFnPtr, f: fn(OpaqueFnPtr) -> u64) -> Self {
+    pub fn new(reg_id: u64, lbl: L, f: BenFn, thr: Thr, drp: Drp, bat: u32) -> Self {
         BenBld {
             reg_id,
             lbl,
-            fn_ptr,
             f,
+            thr,
+            drp,
+            bat,
+            gen: None,
+            cld: Cld::default(),
+            fls: None,
         }
     }
+    /// Runs a batch of the benchmark function.
+    ///
+    /// `smp` hooks run after setup and before the start timestamp,
+    /// and after the stop timestamp before teardown.
+    /// Returns ellapsed cycles, and drop cycles when measured separately,
+    /// for the whole batch.
     #[inline]
-    pub fn run(&self) -> u64 {
-        (self.f)(self.fn_ptr)
+    pub fn run(&mut self, smp: &mut Smp) -> (u64, u64) {
+        (self.f)(self.drp, self.bat, smp)
+    }
+    /// Returns counts processed per call.
+    pub fn cnts(&self) -> Result<Cnts> {
+        self.thr.cnts(self.lbl)
     }
 }
 impl<L> fmt::Debug for BenBld<L>
@@ -463,21 +1459,39 @@ where
     }
 }
 
+/// Results of a query run.
 #[derive(Debug)]
 pub struct Qry<L>
 where
     L: Label,
 {
-    // pub sels: HashMap<u64, SelBld<L>>,
-    pub phn: PhantomData<L>,
-}
-impl<L> Qry<L>
-where
-    L: Label,
-{
-    pub fn new() -> Self {
-        Qry { phn: PhantomData }
-    }
+    /// Selections mapped by selection id.
+    pub sels: HashMap<u64, Sel<L>>,
+    /// Comparisons in the order they were built.
+    pub cmps: Vec<Cmp<L>>,
+    /// Multi-selection comparisons in the order they were built.
+    pub mlts: Vec<Mlt<L>>,
+    /// Normalized series in the order they were built.
+    pub nrms: Vec<Nrm<L>>,
+    /// Complexity fits in the order they were built.
+    pub fits: Vec<Fit<L>>,
+    /// Roofline comparisons in the order they were built.
+    pub rfls: Vec<Rfl<L>>,
+    /// Benchmark metrics with high outlier rates, reported once each.
+    pub otl_wrns: Vec<OtlWrn<L>>,
+    /// Benchmarks with medians within overhead noise.
+    pub nses: Vec<Nse<L>>,
+    /// Measured timestamp counter rate in cycles per second,
+    /// when a selected benchmark declares throughput counts.
+    pub hz: Option<f64>,
+    /// Cache topology of the host, when readable.
+    pub cch: Option<Cch>,
+    /// Sample schedule, which records the shuffle seed.
+    pub sch: Sch,
+    /// Environment checks read before measuring.
+    pub env: Env,
+    /// Context switch contamination of each benchmark, when checked.
+    pub pmts: Vec<Pmt<L>>,
 }
 #[derive(Debug)]
 pub struct Reg<L>
@@ -486,6 +1500,8 @@ where
 {
     pub lbls: Vec<L>,
     pub bens: Vec<Ben<L>>,
+    /// Spacing of struct label values.
+    pub spc: Spc,
 }
 impl<L> Reg<L>
 where
@@ -495,6 +1511,7 @@ where
         Reg {
             lbls: lbls.to_vec(),
             bens: Vec::new(),
+            spc: Spc::default(),
         }
     }
 }
@@ -504,14 +1521,73 @@ where
     L: Label,
 {
     pub lbl: L,
-    pub vals: Vec<u64>,
+    /// A summary of benchmark values shared by selections.
+    pub smry: Arc<Smry>,
+    /// Counts processed per call.
+    pub cnts: Cnts,
+    /// A summary of drop cycles when measured separately.
+    pub drp_smry: Option<Arc<Smry>>,
+    /// Whether the median is within timer noise of the overhead.
+    pub nse: bool,
+    /// Input data generator, which reproduces the inputs.
+    pub gen: Option<Gen>,
+    /// Cache and branch predictor state before each sample.
+    pub cld: Cld,
+    /// Whether each kept sample was preempted by a context switch, when checked.
+    pub csws: Vec<bool>,
+    /// Count of preempted samples, including discarded samples.
+    pub csw_cnt: u32,
+    /// Count of samples run, including discarded samples.
+    pub smp_cnt: u32,
+    /// Summaries of resource usage, in `Mtr::RUS` order, when read.
+    pub rus_smrys: Vec<Arc<Smry>>,
+    /// Resource usage summed over samples run, including discarded samples.
+    pub rus_sums: RusVals,
 }
 impl<L> Ben<L>
 where
     L: Label,
 {
-    pub fn new(lbl: L, vals: Vec<u64>) -> Self {
-        Ben { lbl, vals }
+    /// Returns a new benchmark result.
+    ///
+    /// Outliers are excluded from the summary when `exc_otl` is true.
+    pub fn new(lbl: L, vals: Vec<u64>, exc_otl: bool) -> Self {
+        Ben {
+            lbl,
+            smry: Arc::new(Smry::new(vals, exc_otl)),
+            cnts: Cnts::default(),
+            drp_smry: None,
+            nse: false,
+            gen: None,
+            cld: Cld::default(),
+            csws: Vec::new(),
+            csw_cnt: 0,
+            smp_cnt: 0,
+            rus_smrys: Vec::new(),
+            rus_sums: RusVals::default(),
+        }
+    }
+    /// Returns the summary of a metric, when measured.
+    pub fn mtr_smry(&self, mtr: Mtr) -> Option<&Arc<Smry>> {
+        match mtr {
+            Mtr::Cyc => Some(&self.smry),
+            Mtr::Drp => self.drp_smry.as_ref(),
+            _ => self.rus_smrys.get(mtr.rus_idx()?),
+        }
+    }
+    /// Returns resource usage per sample, in `Mtr::RUS` order, when read.
+    ///
+    /// Page faults are medians. CPU times are means over samples run,
+    /// since per-sample times are mostly zero at scheduler tick granularity.
+    pub fn rus_vals(&self) -> Option<RusVals> {
+        if self.rus_smrys.len() != Mtr::RUS.len() {
+            return None;
+        }
+        let smp_cnt = (self.smp_cnt as u64).max(1);
+        Some(std::array::from_fn(|n| match Mtr::RUS[n] {
+            Mtr::Usr | Mtr::Sys => self.rus_sums[n] / smp_cnt,
+            _ => self.rus_smrys[n].mdn,
+        }))
     }
 }
 #[derive(Debug)]
@@ -521,6 +1597,13 @@ where
 {
     pub lbls: Vec<L>,
     pub sta: Sta,
+    pub mtr: Mtr,
+    /// Spacing of struct label values.
+    pub spc: Spc,
+    /// Cache and branch predictor state of the benchmarks.
+    pub cld: Cld,
+    /// Input data generators of the benchmarks.
+    pub gen: SelGen,
     pub vals: Vec<StaVal<L>>,
 }
 
@@ -532,9 +1615,42 @@ where
         Sel {
             lbls: lbls.to_vec(),
             sta,
+            mtr: Mtr::default(),
+            spc: Spc::default(),
+            cld: Cld::default(),
+            gen: SelGen::default(),
             vals,
         }
     }
+    /// Returns the statistical value of a benchmark label.
+    ///
+    /// Values are expected to be sorted by label.
+    pub fn val(&self, lbl: L) -> Option<&StaVal<L>> {
+        self.vals
+            .binary_search_by_key(&lbl, |x| x.lbl)
+            .ok()
+            .map(|idx| &self.vals[idx])
+    }
+    /// Validates that another selection has equal labels at each index.
+    ///
+    /// Values are expected to be sorted by label.
+    pub fn chk_aln(&self, oth: &Sel<L>) -> Result<()> {
+        // Validate that labels have equal lengths.
+        if self.vals.len() != oth.vals.len() {
+            bail!(
+                "uneven selection lengths: (a len:{}, b len:{})",
+                self.vals.len(),
+                oth.vals.len()
+            )
+        }
+        // Validate each label by index.
+        for (idx, (a, b)) in self.vals.iter().zip(oth.vals.iter()).enumerate() {
+            if a.lbl != b.lbl {
+                bail!("unequal labels: idx:{} (a:{}, b:{})", idx, a.lbl, b.lbl)
+            }
+        }
+        Ok(())
+    }
 }
 #[derive(Debug)]
 pub struct Cmp<L>
@@ -544,30 +1660,574 @@ where
     pub hdr_lbls: Vec<L>,
     pub a_lbls: Vec<L>,
     pub b_lbls: Vec<L>,
-    pub a_vals: Vec<u64>,
-    pub b_vals: Vec<u64>,
-    pub ratios: Vec<f32>,
+    /// Values of "a" at each index.
+    ///
+    /// Values are missing when outer alignment finds no matching label.
+    pub a_vals: Vec<Option<u64>>,
+    /// Values of "b" at each index.
+    ///
+    /// Values are missing when outer alignment finds no matching label.
+    pub b_vals: Vec<Option<u64>>,
+    pub ratios: Vec<Option<f32>>,
+    /// Bootstrap confidence intervals of `ratios`.
+    pub ratio_cis: Vec<Option<Ci>>,
+    /// Bootstrap confidence intervals of `a_vals`.
+    pub a_cis: Vec<Option<Ci>>,
+    /// Bootstrap confidence intervals of `b_vals`.
+    pub b_cis: Vec<Option<Ci>>,
+    /// Summaries of the raw benchmark values behind `a_vals`.
+    pub a_smrys: Vec<Option<Arc<Smry>>>,
+    /// Summaries of the raw benchmark values behind `b_vals`.
+    pub b_smrys: Vec<Option<Arc<Smry>>>,
+    /// Throughput of `a_vals`.
+    pub a_tpts: Vec<Option<Tpt>>,
+    /// Throughput of `b_vals`.
+    pub b_tpts: Vec<Option<Tpt>>,
+    /// Resource usage per sample of "a", in `Mtr::RUS` order.
+    pub a_russ: Vec<Option<RusVals>>,
+    /// Resource usage per sample of "b", in `Mtr::RUS` order.
+    pub b_russ: Vec<Option<RusVals>>,
+    /// Significance tests between raw benchmark values at each index.
+    pub sigs: Vec<Option<Sig>>,
+    /// Significance level of `sigs`.
+    pub alp: f64,
+    /// Speedup of "b" relative to "a" at each index, `a / b`.
+    ///
+    /// Values greater than one indicate "b" is faster.
+    pub spds: Vec<Option<f32>>,
+    /// Percentage change of "b" relative to "a" at each index, `(b - a) / a * 100`.
+    ///
+    /// Negative values indicate "b" is faster.
+    pub pcts: Vec<Option<f32>>,
+    /// Winner at each index.
+    pub wins: Vec<Option<Win>>,
+    /// Crossovers where the faster side flips, in label order.
+    pub xovrs: Vec<Xovr<L>>,
+    /// Memory level the working set fits in at each index.
+    ///
+    /// Present when the benchmarks declare bytes and the cache topology is known.
+    pub mems: Vec<Option<Mem>>,
+    /// Statistical function of "a".
+    pub a_sta: Sta,
+    /// Statistical function of "b".
+    pub b_sta: Sta,
+    /// Metric of "a".
+    pub a_mtr: Mtr,
+    /// Metric of "b".
+    pub b_mtr: Mtr,
+    /// Cache state of "a".
+    pub a_cld: Cld,
+    /// Input data generators of "a".
+    pub a_gen: SelGen,
+    /// Cache state of "b".
+    pub b_cld: Cld,
+    /// Input data generators of "b".
+    pub b_gen: SelGen,
+    /// Whether tables highlight winners with colour.
+    pub clr: bool,
+    /// Whether tables show resource usage rows.
+    pub rus: bool,
 }
 impl<L> Cmp<L>
 where
     L: Label,
 {
+    /// Returns a comparison of two selections.
+    ///
+    /// Selection values are aligned by label with the query alignment mode.
+    /// Derived values are calculated where both selections have a value.
+    ///
+    /// Confidence intervals are bootstrapped when the query specifies bootstrap settings.
+    ///
+    /// Working sets are annotated with a memory level when a cache topology is given.
     pub fn new(
-        hdr_lbls: Vec<L>,
-        a_lbls: Vec<L>,
-        b_lbls: Vec<L>,
-        a_vals: Vec<u64>,
-        b_vals: Vec<u64>,
-        ratios: Vec<f32>,
-    ) -> Self {
-        Cmp {
+        a_sel: &Sel<L>,
+        b_sel: &Sel<L>,
+        qry_bld: &QryBld<L>,
+        cch: Option<&Cch>,
+    ) -> Result<Self> {
+        // Align selection values by label.
+        let (hdr_lbls, cols) = aln(&[a_sel, b_sel], qry_bld.aln)?;
+        let (a_cols, b_cols) = (&cols[0], &cols[1]);
+
+        // Create comparison data.
+        let a_lbls: Vec<L> = a_sel.lbls.clone();
+        let b_lbls: Vec<L> = b_sel.lbls.clone();
+        let a_vals: Vec<Option<u64>> = a_cols.iter().map(|x| x.map(|y| y.val)).collect();
+        let b_vals: Vec<Option<u64>> = b_cols.iter().map(|x| x.map(|y| y.val)).collect();
+        let a_cis: Vec<Option<Ci>> = a_cols.iter().map(|x| x.and_then(|y| y.ci)).collect();
+        let b_cis: Vec<Option<Ci>> = b_cols.iter().map(|x| x.and_then(|y| y.ci)).collect();
+        let a_smrys: Vec<Option<Arc<Smry>>> =
+            a_cols.iter().map(|x| x.map(|y| y.smry.clone())).collect();
+        let b_smrys: Vec<Option<Arc<Smry>>> =
+            b_cols.iter().map(|x| x.map(|y| y.smry.clone())).collect();
+        let a_tpts: Vec<Option<Tpt>> = a_cols.iter().map(|x| x.and_then(|y| y.tpt)).collect();
+        let b_tpts: Vec<Option<Tpt>> = b_cols.iter().map(|x| x.and_then(|y| y.tpt)).collect();
+        let a_russ: Vec<Option<RusVals>> = a_cols.iter().map(|x| x.and_then(|y| y.rus)).collect();
+        let b_russ: Vec<Option<RusVals>> = b_cols.iter().map(|x| x.and_then(|y| y.rus)).collect();
+
+        let len = hdr_lbls.len();
+        let mut ratios: Vec<Option<f32>> = vec![None; len];
+        let mut ratio_cis: Vec<Option<Ci>> = vec![None; len];
+        let mut sigs: Vec<Option<Sig>> = vec![None; len];
+        let mut spds: Vec<Option<f32>> = vec![None; len];
+        let mut pcts: Vec<Option<f32>> = vec![None; len];
+        let mut wins: Vec<Option<Win>> = vec![None; len];
+        for n in 0..len {
+            let (Some(a_val), Some(b_val)) = (a_vals[n], b_vals[n]) else {
+                continue;
+            };
+            let a_smry = a_smrys[n].as_ref().unwrap();
+            let b_smry = b_smrys[n].as_ref().unwrap();
+
+            // Calculate the ratio of values.
+            let a = a_val as f32;
+            let b = b_val as f32;
+            let (mut min, max) = if a < b { (a, b) } else { (b, a) };
+            min = min.max(1.0);
+            ratios[n] = Some(f32_pnt_one(max / min));
+
+            // Bootstrap a ratio confidence interval with a reproducible seed.
+            if let Some(bts) = qry_bld.bts.as_ref() {
+                let mut rng = Rng::new_mix(bts.sed, (&a_lbls, &b_lbls, hdr_lbls[n]));
+                ratio_cis[n] = Some(bts.ci_rto(a_smry, a_sel.sta, b_smry, b_sel.sta, &mut rng));
+            }
+
+            // Test the significance of differences between raw benchmark values.
+            let sig = Sig::new(a_smry, b_smry);
+            sigs[n] = Some(sig);
+
+            // Calculate the direction of change.
+            // Lower values are better.
+            let a = a.max(1.0);
+            let b = b.max(1.0);
+            spds[n] = Some(a / b);
+            pcts[n] = Some((b - a) / a * 100.0);
+            wins[n] = Some(if a_val == b_val || !sig.is_sig(qry_bld.alp) {
+                Win::Tie
+            } else if a_val < b_val {
+                Win::A
+            } else {
+                Win::B
+            });
+        }
+
+        // Detect crossovers where the faster side flips along the struct label.
+        // Log spaced sweeps interpolate geometrically.
+        let spc = if a_sel.spc == b_sel.spc {
+            a_sel.spc
+        } else {
+            Spc::Lin
+        };
+        let xovrs = Xovr::dtc(&hdr_lbls, &a_vals, &b_vals, &wins, spc);
+
+        // Find the memory level of the larger working set.
+        let mems: Vec<Option<Mem>> = match cch {
+            None => vec![None; len],
+            Some(cch) => a_cols
+                .iter()
+                .zip(b_cols.iter())
+                .map(|(a, b)| {
+                    let a_byt = a.and_then(|x| x.byt);
+                    let b_byt = b.and_then(|x| x.byt);
+                    a_byt.max(b_byt).map(|x| cch.mem(x))
+                })
+                .collect(),
+        };
+
+        Ok(Cmp {
             hdr_lbls,
             a_lbls,
             b_lbls,
             a_vals,
             b_vals,
             ratios,
+            ratio_cis,
+            a_cis,
+            b_cis,
+            a_smrys,
+            b_smrys,
+            a_tpts,
+            b_tpts,
+            a_russ,
+            b_russ,
+            sigs,
+            alp: qry_bld.alp,
+            spds,
+            pcts,
+            wins,
+            xovrs,
+            mems,
+            a_sta: a_sel.sta,
+            b_sta: b_sel.sta,
+            a_mtr: a_sel.mtr,
+            b_mtr: b_sel.mtr,
+            a_cld: a_sel.cld,
+            b_cld: b_sel.cld,
+            a_gen: a_sel.gen,
+            b_gen: b_sel.gen,
+            clr: qry_bld.clr,
+            rus: qry_bld.rus,
+        })
+    }
+    /// Returns the count of wins for a side.
+    pub fn win_cnt(&self, win: Win) -> usize {
+        self.wins.iter().filter(|x| **x == Some(win)).count()
+    }
+    /// Returns the geometric mean of speedups.
+    pub fn geo_spd(&self) -> f32 {
+        let spds: Vec<f32> = self.spds.iter().flatten().copied().collect();
+        if spds.is_empty() {
+            return 1.0;
+        }
+        let ln_sum: f64 = spds.iter().map(|x| (*x as f64).ln()).sum();
+        (ln_sum / spds.len() as f64).exp() as f32
+    }
+}
+
+/// A comparison of multiple selections.
+#[derive(Debug)]
+pub struct Mlt<L>
+where
+    L: Label,
+{
+    pub hdr_lbls: Vec<L>,
+    /// Labels of each selection.
+    pub lblss: Vec<Vec<L>>,
+    /// Statistical functions of each selection.
+    pub stas: Vec<Sta>,
+    /// Metrics of each selection.
+    pub mtrs: Vec<Mtr>,
+    /// Cache states of each selection.
+    pub clds: Vec<Cld>,
+    /// Input data generators of each selection.
+    pub gens: Vec<SelGen>,
+    /// Statistical values of each selection.
+    ///
+    /// Values are missing when outer alignment finds no matching label.
+    pub valss: Vec<Vec<Option<u64>>>,
+    /// Bootstrap confidence intervals of each selection.
+    pub ciss: Vec<Vec<Option<Ci>>>,
+    /// Summaries of the raw benchmark values of each selection.
+    pub smryss: Vec<Vec<Option<Arc<Smry>>>>,
+    /// Throughput of each selection.
+    pub tptss: Vec<Vec<Option<Tpt>>>,
+    /// Index of the reference selection.
+    pub ref_idx: usize,
+    /// Ratios of each selection to the reference selection, `val / ref`.
+    pub ratioss: Vec<Vec<Option<f32>>>,
+    /// Ranks of each selection at each index.
+    ///
+    /// Rank one is the fastest. Equal values share a rank.
+    pub rnkss: Vec<Vec<Option<u16>>>,
+    /// Whether tables highlight the fastest selections with colour.
+    pub clr: bool,
+}
+impl<L> Mlt<L>
+where
+    L: Label,
+{
+    /// Returns a comparison of multiple selections.
+    ///
+    /// Selection values are aligned by label with the query alignment mode.
+    pub fn new(sels: &[&Sel<L>], ref_idx: usize, qry_bld: &QryBld<L>) -> Result<Self> {
+        // Align selection values by label.
+        let (hdr_lbls, cols) = aln(sels, qry_bld.aln)?;
+
+        let lblss: Vec<Vec<L>> = sels.iter().map(|x| x.lbls.clone()).collect();
+        let stas: Vec<Sta> = sels.iter().map(|x| x.sta).collect();
+        let mtrs: Vec<Mtr> = sels.iter().map(|x| x.mtr).collect();
+        let clds: Vec<Cld> = sels.iter().map(|x| x.cld).collect();
+        let gens: Vec<SelGen> = sels.iter().map(|x| x.gen).collect();
+        let valss: Vec<Vec<Option<u64>>> = cols
+            .iter()
+            .map(|x| x.iter().map(|y| y.map(|z| z.val)).collect())
+            .collect();
+        let ciss: Vec<Vec<Option<Ci>>> = cols
+            .iter()
+            .map(|x| x.iter().map(|y| y.and_then(|z| z.ci)).collect())
+            .collect();
+        let smryss: Vec<Vec<Option<Arc<Smry>>>> = cols
+            .iter()
+            .map(|x| x.iter().map(|y| y.map(|z| z.smry.clone())).collect())
+            .collect();
+        let tptss: Vec<Vec<Option<Tpt>>> = cols
+            .iter()
+            .map(|x| x.iter().map(|y| y.and_then(|z| z.tpt)).collect())
+            .collect();
+
+        // Calculate ratios to the reference selection.
+        let ratioss: Vec<Vec<Option<f32>>> = valss
+            .iter()
+            .map(|vals| {
+                vals.iter()
+                    .zip(valss[ref_idx].iter())
+                    .map(|(v, r)| match (v, r) {
+                        (Some(v), Some(r)) => Some(f32_pnt_one(*v as f32 / (*r as f32).max(1.0))),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Rank selections at each index.
+        // Lower values are better.
+        let mut rnkss: Vec<Vec<Option<u16>>> = vec![vec![None; hdr_lbls.len()]; valss.len()];
+        for n in 0..hdr_lbls.len() {
+            for (m, vals) in valss.iter().enumerate() {
+                if let Some(val) = vals[n] {
+                    let fst_cnt = valss
+                        .iter()
+                        .filter(|x| x[n].is_some_and(|y| y < val))
+                        .count();
+                    rnkss[m][n] = Some(1 + fst_cnt as u16);
+                }
+            }
+        }
+
+        Ok(Mlt {
+            hdr_lbls,
+            lblss,
+            stas,
+            mtrs,
+            clds,
+            gens,
+            valss,
+            ciss,
+            smryss,
+            tptss,
+            ref_idx,
+            ratioss,
+            rnkss,
+            clr: qry_bld.clr,
+        })
+    }
+}
+
+/// Selections normalized to a reference selection.
+#[derive(Debug)]
+pub struct Nrm<L>
+where
+    L: Label,
+{
+    pub hdr_lbls: Vec<L>,
+    /// Labels of the reference selection.
+    pub ref_lbls: Vec<L>,
+    /// Statistical function of the reference selection.
+    pub ref_sta: Sta,
+    /// Metric of the reference selection.
+    pub ref_mtr: Mtr,
+    /// Normalized series of each selection.
+    pub sers: Vec<NrmSer<L>>,
+}
+impl<L> Nrm<L>
+where
+    L: Label,
+{
+    /// Returns selections normalized to a reference selection.
+    ///
+    /// Selection values are aligned by label with the query alignment mode.
+    pub fn new(ref_sel: &Sel<L>, sels: &[&Sel<L>], qry_bld: &QryBld<L>) -> Result<Self> {
+        // Align selection values by label.
+        // The reference selection is the first series.
+        let mut all_sels: Vec<&Sel<L>> = Vec::with_capacity(1 + sels.len());
+        all_sels.push(ref_sel);
+        all_sels.extend(sels);
+        let (hdr_lbls, cols) = aln(&all_sels, qry_bld.aln)?;
+
+        let sers: Vec<NrmSer<L>> = all_sels
+            .iter()
+            .zip(cols.iter())
+            .map(|(sel, col)| NrmSer::new(sel, col, &cols[0]))
+            .collect();
+        Ok(Nrm {
+            hdr_lbls,
+            ref_lbls: ref_sel.lbls.clone(),
+            ref_sta: ref_sel.sta,
+            ref_mtr: ref_sel.mtr,
+            sers,
+        })
+    }
+}
+
+/// A selection normalized to a reference selection.
+#[derive(Debug)]
+pub struct NrmSer<L>
+where
+    L: Label,
+{
+    pub lbls: Vec<L>,
+    pub sta: Sta,
+    pub mtr: Mtr,
+    pub cld: Cld,
+    pub gen: SelGen,
+    /// Multiples of the reference selection values.
+    ///
+    /// Values are missing when either selection has no value.
+    pub vals: Vec<Option<f32>>,
+}
+impl<L> NrmSer<L>
+where
+    L: Label,
+{
+    /// Returns a selection normalized to aligned reference values.
+    pub fn new(sel: &Sel<L>, col: &[Option<&StaVal<L>>], ref_col: &[Option<&StaVal<L>>]) -> Self {
+        let vals: Vec<Option<f32>> = col
+            .iter()
+            .zip(ref_col.iter())
+            .map(|(v, r)| match (v, r) {
+                (Some(v), Some(r)) => Some(v.val as f32 / (r.val as f32).max(1.0)),
+                _ => None,
+            })
+            .collect();
+        NrmSer {
+            lbls: sel.lbls.clone(),
+            sta: sel.sta,
+            mtr: sel.mtr,
+            cld: sel.cld,
+            gen: sel.gen,
+            vals,
+        }
+    }
+}
+
+/// Aligned selection values.
+///
+/// Struct labels, and the values of each selection at each struct label.
+pub type AlnCols<'a, L> = (Vec<L>, Vec<Vec<Option<&'a StaVal<L>>>>);
+
+/// Aligns the values of selections by label.
+///
+/// Selection values are expected to be sorted by label.
+pub fn aln<'a, L>(sels: &[&'a Sel<L>], aln: Aln) -> Result<AlnCols<'a, L>>
+where
+    L: Label,
+{
+    let hdr_lbls: Vec<L> = match aln {
+        Aln::Str => {
+            // Validate labels of every selection against the first selection.
+            for sel in sels.iter().skip(1) {
+                sels[0].chk_aln(sel)?;
+            }
+            sels[0].vals.iter().map(|x| x.lbl).collect()
         }
+        Aln::Inr => sels[0]
+            .vals
+            .iter()
+            .map(|x| x.lbl)
+            .filter(|lbl| sels.iter().all(|sel| sel.val(*lbl).is_some()))
+            .collect(),
+        Aln::Otr => {
+            let mut lbls: Vec<L> = sels
+                .iter()
+                .flat_map(|sel| sel.vals.iter().map(|x| x.lbl))
+                .collect();
+            lbls.sort_unstable();
+            lbls.dedup();
+            lbls
+        }
+    };
+    if hdr_lbls.is_empty() {
+        bail!("empty alignment: selections share no labels");
+    }
+    let cols = sels
+        .iter()
+        .map(|sel| hdr_lbls.iter().map(|lbl| sel.val(*lbl)).collect())
+        .collect();
+    Ok((hdr_lbls, cols))
+}
+
+/// A mode which aligns selection values by label for comparison.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Aln {
+    /// Selections are required to have identical labels.
+    #[default]
+    Str,
+    /// Labels shared by every selection are compared.
+    Inr,
+    /// Every label of any selection is compared.
+    ///
+    /// Missing values are left blank.
+    Otr,
+}
+
+/// The winner of a comparison at a single index.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Win {
+    /// Values are equal, or the difference isn't significant.
+    #[default]
+    Tie,
+    /// Selection "a" is faster.
+    A,
+    /// Selection "b" is faster.
+    B,
+}
+
+/// A crossover where the faster side of a comparison flips.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Xovr<L>
+where
+    L: Label,
+{
+    /// Last label before the crossover.
+    pub lo_lbl: L,
+    /// First label after the crossover.
+    pub hi_lbl: L,
+    /// Interpolated struct label value where values are equal.
+    pub val: f64,
+    /// Faster side below the crossover.
+    pub blw: Win,
+    /// Faster side above the crossover.
+    pub abv: Win,
+}
+impl<L> Xovr<L>
+where
+    L: Label,
+{
+    /// Returns crossovers detected between two aligned series.
+    ///
+    /// Only indexes with a significant winner are considered, so ties and
+    /// missing values don't end a run.
+    /// The crossover value is interpolated between neighbouring labels with the series spacing.
+    /// Labels without a numeric value have no crossovers.
+    pub fn dtc(
+        hdr_lbls: &[L],
+        a_vals: &[Option<u64>],
+        b_vals: &[Option<u64>],
+        wins: &[Option<Win>],
+        spc: Spc,
+    ) -> Vec<Xovr<L>> {
+        let mut xovrs: Vec<Xovr<L>> = Vec::new();
+        // Previous label, label value, difference and winner.
+        let mut prv: Option<(L, f64, f64, Win)> = None;
+        for n in 0..hdr_lbls.len() {
+            let (Some(a_val), Some(b_val), Some(win)) = (a_vals[n], b_vals[n], wins[n]) else {
+                continue;
+            };
+            if win == Win::Tie {
+                continue;
+            }
+            let Result::Ok(val) = hdr_lbls[n].val() else {
+                return Vec::new();
+            };
+            let val = val as f64;
+            let dif = a_val as f64 - b_val as f64;
+            if let Some((prv_lbl, prv_val, prv_dif, prv_win)) = prv {
+                if prv_win != win {
+                    xovrs.push(Xovr {
+                        lo_lbl: prv_lbl,
+                        hi_lbl: hdr_lbls[n],
+                        val: spc.itp(prv_val, val, prv_dif / (prv_dif - dif)),
+                        blw: prv_win,
+                        abv: win,
+                    });
+                }
+            }
+            prv = Some((hdr_lbls[n], val, dif, win));
+        }
+        xovrs
     }
 }
 
@@ -582,17 +2242,149 @@ where
     /// These may be different from selection labels.
     ///
     /// Benchmark labels may have more labels than selection labels.
-    lbl: L,
+    pub lbl: L,
     /// A benchmark value returned from a statistical function.
-    val: u64,
+    pub val: u64,
+    /// A summary of the raw benchmark values.
+    pub smry: Arc<Smry>,
+    /// A bootstrap confidence interval of the value.
+    pub ci: Option<Ci>,
+    /// Throughput of the value when the benchmark declares counts.
+    pub tpt: Option<Tpt>,
+    /// Input data generator of the benchmark, which reproduces the inputs.
+    pub gen: Option<Gen>,
+    /// Working set bytes of the benchmark when declared.
+    pub byt: Option<u64>,
+    /// Resource usage per sample, in `Mtr::RUS` order.
+    pub rus: Option<RusVals>,
 }
 impl<L> StaVal<L>
 where
     L: Label,
 {
     /// Returns a new statisitcal value.
-    pub fn new(lbl: L, val: u64) -> Self {
-        StaVal { lbl, val }
+    pub fn new(lbl: L, val: u64, smry: Arc<Smry>) -> Self {
+        StaVal {
+            lbl,
+            val,
+            smry,
+            ci: None,
+            tpt: None,
+            gen: None,
+            byt: None,
+            rus: None,
+        }
+    }
+}
+
+/// Drop timing of a value returned by a benchmark function.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Drp {
+    /// Drops the returned value after the stop timestamp.
+    Exc,
+    /// Drops the returned value before the stop timestamp,
+    /// including deallocation cost in measurements.
+    #[default]
+    Inc,
+    /// Drops the returned value after the stop timestamp,
+    /// and measures drop cycles as a separate metric.
+    Sep,
+}
+
+/// A metric recorded for each benchmark run.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Mtr {
+    /// Cycles of the benchmark function.
+    #[default]
+    Cyc,
+    /// Cycles dropping the returned value.
+    ///
+    /// Requires `Drp::Sep` on the registration.
+    Drp,
+    /// Minor page faults of each sample.
+    MinFlt,
+    /// Major page faults of each sample.
+    MajFlt,
+    /// Peak resident set size growth in bytes of each sample.
+    ///
+    /// Read from the process high-water mark, reset before each sample.
+    /// The mark is process-wide, so concurrent workers also raise and reset it.
+    Rss,
+    /// User CPU time in microseconds of each sample.
+    ///
+    /// Mostly zero at scheduler tick granularity, so selections reject `Mdn`,
+    /// and `Avg` is the mean over samples run, as tables report.
+    Usr,
+    /// System CPU time in microseconds of each sample.
+    ///
+    /// Selected like `Usr`.
+    Sys,
+}
+impl Mtr {
+    /// Resource usage metrics read with `getrusage`.
+    pub const RUS: [Mtr; 5] = [Mtr::MinFlt, Mtr::MajFlt, Mtr::Rss, Mtr::Usr, Mtr::Sys];
+
+    /// Returns the index of a resource usage metric in `Mtr::RUS`.
+    pub fn rus_idx(&self) -> Option<usize> {
+        Mtr::RUS.iter().position(|x| x == self)
+    }
+}
+impl fmt::Display for Mtr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mtr::Cyc => write!(f, "cycles"),
+            Mtr::Drp => write!(f, "drop"),
+            Mtr::MinFlt => write!(f, "minor faults"),
+            Mtr::MajFlt => write!(f, "major faults"),
+            Mtr::Rss => write!(f, "rss growth B"),
+            Mtr::Usr => write!(f, "user µs"),
+            Mtr::Sys => write!(f, "sys µs"),
+        }
+    }
+}
+
+/// An order of benchmark samples within a worker.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Sch {
+    /// All samples of each benchmark run before the next benchmark.
+    #[default]
+    Seq,
+    /// One sample of each benchmark runs in turn, e.g. `ABAB`.
+    Ilv,
+    /// One sample of each benchmark runs in a shuffled order each round.
+    ///
+    /// The seed reproduces the order.
+    Shf(u64),
+}
+impl Sch {
+    /// Returns benchmark indexes in sample order.
+    ///
+    /// `grp` distinguishes the shuffle of each worker.
+    pub fn ord(&self, cnt: usize, itr: u16, grp: usize) -> Vec<usize> {
+        let mut idxs: Vec<usize> = Vec::with_capacity(cnt * itr as usize);
+        match self {
+            Sch::Seq => {
+                for idx in 0..cnt {
+                    idxs.extend(std::iter::repeat_n(idx, itr as usize));
+                }
+            }
+            Sch::Ilv => {
+                for _ in 0..itr {
+                    idxs.extend(0..cnt);
+                }
+            }
+            Sch::Shf(sed) => {
+                let mut rng = Rng::new_mix(*sed, grp);
+                let mut rnd: Vec<usize> = (0..cnt).collect();
+                for _ in 0..itr {
+                    rng.shuffle(&mut rnd);
+                    idxs.extend(rnd.iter());
+                }
+            }
+        }
+        idxs
     }
 }
 
@@ -610,6 +2402,28 @@ pub enum Sta {
     /// Average benchmark value.
     Avg,
 }
+impl Sta {
+    /// Returns the statistical value of unsorted benchmark values.
+    ///
+    /// Values may be reordered.
+    pub fn cal(&self, vals: &mut [u64]) -> u64 {
+        if vals.is_empty() {
+            return 0;
+        }
+        match self {
+            Mdn => {
+                let mdl = vals.len() / 2;
+                *vals.select_nth_unstable(mdl).1
+            }
+            Avg => {
+                let len = vals.len() as u64;
+                vals.iter().sum::<u64>().saturating_div(len)
+            }
+            Min => *vals.iter().min().unwrap(),
+            Max => *vals.iter().max().unwrap(),
+        }
+    }
+}
 
 /// A label used to aggregate, filter, and sort benchmark functions.
 pub trait Label:
@@ -623,6 +2437,13 @@ pub trait Label:
 pub trait EnumStructVal {
     /// `val` returns an inner struct value from an enum.
     fn val(&self) -> Result<u32>;
+    /// `hdr` returns a table header name of the inner struct value.
+    ///
+    /// Defaults to the formatted value. Override for values
+    /// which aren't numbers, such as a type name.
+    fn hdr(&self) -> String {
+        self.val().map(fmt_num).unwrap_or_default()
+    }
 }
 
 /// Measures the ellapsed time of processor instructions.
@@ -693,6 +2514,79 @@ pub fn overhead_cpu_cyc() -> u64 {
     overhead
 }
 
+/// Overhead of x86 timestamp instructions, and its noise.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct Ovh {
+    /// Minimum overhead in cycles.
+    pub min: u64,
+    /// Spread of overhead from the minimum to the 90th percentile, in cycles.
+    pub nse: u64,
+}
+impl Ovh {
+    /// Returns overhead calibrated from repeated timestamp measurements.
+    pub fn cal() -> Self {
+        const CNT: usize = 256;
+        let mut vals: Vec<u64> = (0..CNT)
+            .map(|_| {
+                let fst = fst_cpu_cyc();
+                lst_cpu_cyc() - fst
+            })
+            .collect();
+        vals.sort_unstable();
+        let min = vals[0];
+        Ovh {
+            min,
+            nse: vals[CNT * 9 / 10] - min,
+        }
+    }
+}
+
+/// A benchmark with a median within overhead noise.
+///
+/// Such benchmarks were probably optimized away.
+#[derive(Debug, Clone)]
+pub struct Nse<L>
+where
+    L: Label,
+{
+    /// Registration labels.
+    pub lbls: Vec<L>,
+    /// Benchmark label.
+    pub lbl: L,
+    /// Median cycles after subtracting overhead.
+    pub mdn: u64,
+}
+impl<L> fmt::Display for Nse<L>
+where
+    L: Label,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "warning: '{},{}' median {} cycles is within overhead noise; probably optimized away",
+            join(&self.lbls, ','),
+            self.lbl,
+            fmt_num(self.mdn)
+        )
+    }
+}
+
+/// A benchmark metric with a high outlier rate.
+#[derive(Debug, Clone)]
+pub struct OtlWrn<L>
+where
+    L: Label,
+{
+    /// Registration labels.
+    pub lbls: Vec<L>,
+    /// Benchmark label.
+    pub lbl: L,
+    /// Metric of the summary.
+    pub mtr: Mtr,
+    /// Summary with the outliers.
+    pub smry: Arc<Smry>,
+}
+
 /// Returns a unique and sorted list of labels.
 pub fn unq_srt<L>(lbls: &[L]) -> Vec<L>
 where
@@ -831,3 +2725,103 @@ pub fn fmt_f32(v: f32) -> String {
 pub fn f32_pnt_one(v: f32) -> f32 {
     format!("{:.1}", v).parse::<f32>().unwrap()
 }
+
+#[cfg(test)]
+mod tst {
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+    enum Lbl {
+        Len(u32),
+        Nam,
+    }
+    impl Label for Lbl {}
+    impl fmt::Display for Lbl {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+    impl EnumStructVal for Lbl {
+        fn val(&self) -> Result<u32> {
+            match self {
+                Lbl::Len(x) => Ok(*x),
+                Lbl::Nam => bail!("no value"),
+            }
+        }
+    }
+
+    fn lens(vals: &[u32]) -> Vec<Lbl> {
+        vals.iter().map(|x| Lbl::Len(*x)).collect()
+    }
+
+    #[test]
+    fn xovr_non() {
+        let hdr_lbls = lens(&[10, 20, 30]);
+        let a = [Some(10), Some(20), Some(30)];
+        let b = [Some(20), Some(40), Some(60)];
+        let wins = [Some(Win::A); 3];
+        assert!(Xovr::dtc(&hdr_lbls, &a, &b, &wins, Spc::Lin).is_empty());
+    }
+
+    #[test]
+    fn xovr_one() {
+        let hdr_lbls = lens(&[10, 20, 30, 40]);
+        let a = [Some(10), Some(20), Some(40), Some(50)];
+        let b = [Some(20), Some(30), Some(30), Some(40)];
+        let wins = [Some(Win::A), Some(Win::A), Some(Win::B), Some(Win::B)];
+        let xovrs = Xovr::dtc(&hdr_lbls, &a, &b, &wins, Spc::Lin);
+        assert_eq!(xovrs.len(), 1);
+        let x = xovrs[0];
+        assert_eq!((x.lo_lbl, x.hi_lbl), (Lbl::Len(20), Lbl::Len(30)));
+        assert_eq!((x.blw, x.abv), (Win::A, Win::B));
+        // Differences of -10 and 10 cross halfway.
+        assert!((x.val - 25.0).abs() < 1e-9, "{}", x.val);
+
+        // Log spacing interpolates geometrically.
+        let hdr_lbls = lens(&[10, 1000]);
+        let a = [Some(10), Some(30)];
+        let b = [Some(20), Some(20)];
+        let wins = [Some(Win::A), Some(Win::B)];
+        let xovrs = Xovr::dtc(&hdr_lbls, &a, &b, &wins, Spc::Log);
+        assert!((xovrs[0].val - 100.0).abs() < 1e-9, "{}", xovrs[0].val);
+    }
+
+    #[test]
+    fn xovr_noisy() {
+        // Ties and missing values don't end a run,
+        // so the crossover spans them.
+        let hdr_lbls = lens(&[10, 20, 30, 40, 50]);
+        let a = [Some(10), Some(20), None, Some(31), Some(60)];
+        let b = [Some(20), Some(21), Some(30), Some(30), Some(40)];
+        let wins = [
+            Some(Win::A),
+            Some(Win::A),
+            None,
+            Some(Win::Tie),
+            Some(Win::B),
+        ];
+        let xovrs = Xovr::dtc(&hdr_lbls, &a, &b, &wins, Spc::Lin);
+        assert_eq!(xovrs.len(), 1);
+        assert_eq!(
+            (xovrs[0].lo_lbl, xovrs[0].hi_lbl),
+            (Lbl::Len(20), Lbl::Len(50))
+        );
+
+        // Significant flips each cross.
+        let wins = [Some(Win::A), Some(Win::B), None, None, Some(Win::A)];
+        let a = [Some(10), Some(30), None, None, Some(10)];
+        let xovrs = Xovr::dtc(&hdr_lbls, &a, &b, &wins, Spc::Lin);
+        assert_eq!(xovrs.len(), 2);
+        assert_eq!((xovrs[1].blw, xovrs[1].abv), (Win::B, Win::A));
+    }
+
+    #[test]
+    fn xovr_no_val() {
+        // Labels without a numeric value have no crossovers.
+        let hdr_lbls = [Lbl::Len(10), Lbl::Nam];
+        let a = [Some(10), Some(30)];
+        let b = [Some(20), Some(20)];
+        let wins = [Some(Win::A), Some(Win::B)];
+        assert!(Xovr::dtc(&hdr_lbls, &a, &b, &wins, Spc::Lin).is_empty());
+    }
+}
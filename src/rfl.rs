@@ -0,0 +1,274 @@
+//! Provides roofline comparisons against calibrated machine peaks.
+
+use crate::*;
+use std::time::Instant;
+
+/// Peak throughput of the host, measured on a single thread.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Pek {
+    /// Peak sequential read bandwidth in bytes per second.
+    pub rd_bw: f64,
+    /// Peak sequential write bandwidth in bytes per second.
+    ///
+    /// Plain stores also read each line for ownership.
+    pub wr_bw: f64,
+    /// Peak scalar floating point operations per second.
+    pub sca_ops: f64,
+    /// Peak SIMD floating point operations per second, with the widest instruction set.
+    pub sim_ops: f64,
+    /// Instruction set of the SIMD peak.
+    pub sim: Sim,
+}
+impl Pek {
+    /// Returns machine peaks measured with calibration kernels.
+    ///
+    /// Bandwidth is measured over a buffer larger than typical last level caches.
+    /// Each measurement is the best of several passes.
+    pub fn cal() -> Self {
+        const BUF_LEN: usize = 1 << 23;
+        const PAS_CNT: usize = 5;
+        const OPS_ITR: usize = 1 << 22;
+        let byt_cnt = (BUF_LEN * mem::size_of::<u64>()) as f64;
+        let mut buf = vec![1u64; BUF_LEN];
+
+        // Measure sequential read bandwidth.
+        let mut rd_bw = 0f64;
+        for _ in 0..PAS_CNT {
+            let fst = Instant::now();
+            let sum = black_box(&buf)
+                .iter()
+                .fold(0u64, |acc, x| acc.wrapping_add(*x));
+            black_box(sum);
+            rd_bw = rd_bw.max(byt_cnt / fst.elapsed().as_secs_f64());
+        }
+
+        // Measure sequential write bandwidth.
+        let mut wr_bw = 0f64;
+        for pas in 0..PAS_CNT {
+            let fst = Instant::now();
+            black_box(&mut buf).fill(pas as u64);
+            black_box(&buf);
+            wr_bw = wr_bw.max(byt_cnt / fst.elapsed().as_secs_f64());
+        }
+
+        // Measure operation rates with independent multiply-add chains.
+        // Each chain performs two operations per lane per iteration.
+        let sim = Sim::dtc();
+        let mut sca_ops = 0f64;
+        let mut sim_ops = 0f64;
+        for _ in 0..PAS_CNT {
+            let fst = Instant::now();
+            black_box(ops_sca(OPS_ITR));
+            sca_ops = sca_ops.max((OPS_ITR * 8 * 2) as f64 / fst.elapsed().as_secs_f64());
+
+            let fst = Instant::now();
+            // Safety: the instruction set was detected on the host.
+            black_box(unsafe {
+                match sim {
+                    Sim::Sse2 => ops_sim(OPS_ITR),
+                    Sim::Avx2 => ops_avx2(OPS_ITR),
+                    Sim::Avx512 => ops_avx512(OPS_ITR),
+                }
+            });
+            let ops = OPS_ITR * 8 * 2 * sim.lns();
+            sim_ops = sim_ops.max(ops as f64 / fst.elapsed().as_secs_f64());
+        }
+
+        Pek {
+            rd_bw,
+            wr_bw,
+            sca_ops,
+            sim_ops,
+            sim,
+        }
+    }
+    /// Returns the arithmetic intensity where SIMD operations become the limit.
+    #[inline]
+    pub fn rdg(&self) -> f64 {
+        self.sim_ops / self.rd_bw
+    }
+    /// Returns attainable SIMD operations per second at an arithmetic intensity.
+    #[inline]
+    pub fn roof(&self, ai: f64) -> f64 {
+        (ai * self.rd_bw).min(self.sim_ops)
+    }
+    /// Returns attainable SIMD operations per second at an arithmetic intensity
+    /// of bytes written.
+    #[inline]
+    pub fn roof_wr(&self, ai: f64) -> f64 {
+        (ai * self.wr_bw).min(self.sim_ops)
+    }
+    /// Returns attainable scalar operations per second at an arithmetic intensity.
+    #[inline]
+    pub fn roof_sca(&self, ai: f64) -> f64 {
+        (ai * self.rd_bw).min(self.sca_ops)
+    }
+}
+
+/// Runs eight scalar multiply-add chains.
+#[inline(never)]
+fn ops_sca(itr: usize) -> f64 {
+    unsafe {
+        let m = x86_64::_mm_set_sd(black_box(0.999_999));
+        let c = x86_64::_mm_set_sd(black_box(1e-9));
+        let mut xs = [x86_64::_mm_set_sd(1.0); 8];
+        for _ in 0..itr {
+            for x in xs.iter_mut() {
+                *x = x86_64::_mm_add_sd(x86_64::_mm_mul_sd(*x, m), c);
+            }
+        }
+        xs.iter().map(|x| x86_64::_mm_cvtsd_f64(*x)).sum()
+    }
+}
+
+/// Runs eight two-lane SIMD multiply-add chains.
+#[inline(never)]
+fn ops_sim(itr: usize) -> f64 {
+    unsafe {
+        let m = x86_64::_mm_set1_pd(black_box(0.999_999));
+        let c = x86_64::_mm_set1_pd(black_box(1e-9));
+        let mut xs = [x86_64::_mm_set1_pd(1.0); 8];
+        for _ in 0..itr {
+            for x in xs.iter_mut() {
+                *x = x86_64::_mm_add_pd(x86_64::_mm_mul_pd(*x, m), c);
+            }
+        }
+        xs.iter().map(|x| x86_64::_mm_cvtsd_f64(*x)).sum()
+    }
+}
+
+/// Runs eight four-lane fused multiply-add chains.
+#[inline(never)]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn ops_avx2(itr: usize) -> f64 {
+    let m = x86_64::_mm256_set1_pd(black_box(0.999_999));
+    let c = x86_64::_mm256_set1_pd(black_box(1e-9));
+    let mut xs = [x86_64::_mm256_set1_pd(1.0); 8];
+    for _ in 0..itr {
+        for x in xs.iter_mut() {
+            *x = x86_64::_mm256_fmadd_pd(*x, m, c);
+        }
+    }
+    xs.iter().map(|x| x86_64::_mm256_cvtsd_f64(*x)).sum()
+}
+
+/// Runs eight eight-lane fused multiply-add chains.
+#[inline(never)]
+#[target_feature(enable = "avx512f")]
+unsafe fn ops_avx512(itr: usize) -> f64 {
+    let m = x86_64::_mm512_set1_pd(black_box(0.999_999));
+    let c = x86_64::_mm512_set1_pd(black_box(1e-9));
+    let mut xs = [x86_64::_mm512_set1_pd(1.0); 8];
+    for _ in 0..itr {
+        for x in xs.iter_mut() {
+            *x = x86_64::_mm512_fmadd_pd(*x, m, c);
+        }
+    }
+    xs.iter().map(|x| x86_64::_mm512_cvtsd_f64(*x)).sum()
+}
+
+/// A SIMD instruction set of the compute peak.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Sim {
+    /// Two-lane multiply and add.
+    #[default]
+    Sse2,
+    /// Four-lane fused multiply-add.
+    Avx2,
+    /// Eight-lane fused multiply-add.
+    Avx512,
+}
+impl Sim {
+    /// Returns the widest instruction set of the host.
+    pub fn dtc() -> Self {
+        if is_x86_feature_detected!("avx512f") {
+            Sim::Avx512
+        } else if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            Sim::Avx2
+        } else {
+            Sim::Sse2
+        }
+    }
+    /// Returns the count of `f64` lanes.
+    pub fn lns(&self) -> usize {
+        match self {
+            Sim::Sse2 => 2,
+            Sim::Avx2 => 4,
+            Sim::Avx512 => 8,
+        }
+    }
+}
+impl fmt::Display for Sim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Sim::Sse2 => write!(f, "sse2"),
+            Sim::Avx2 => write!(f, "avx2"),
+            Sim::Avx512 => write!(f, "avx-512"),
+        }
+    }
+}
+
+/// A roofline comparison of selections against machine peaks.
+#[derive(Debug)]
+pub struct Rfl<L>
+where
+    L: Label,
+{
+    pub hdr_lbls: Vec<L>,
+    /// Labels of each selection.
+    pub lblss: Vec<Vec<L>>,
+    /// Statistical functions of each selection.
+    pub stas: Vec<Sta>,
+    /// Metrics of each selection.
+    pub mtrs: Vec<Mtr>,
+    /// Cache states of each selection.
+    pub clds: Vec<Cld>,
+    /// Input data generators of each selection.
+    pub gens: Vec<SelGen>,
+    /// Throughput of each selection.
+    ///
+    /// Percentages of peak require declared bytes and operations.
+    pub tptss: Vec<Vec<Option<Tpt>>>,
+    /// Calibrated machine peaks.
+    pub pek: Pek,
+}
+impl<L> Rfl<L>
+where
+    L: Label,
+{
+    /// Returns a roofline comparison of selections.
+    ///
+    /// Selection values are aligned by label with the query alignment mode.
+    pub fn new(sels: &[&Sel<L>], pek: Pek, qry_bld: &QryBld<L>) -> Result<Self> {
+        let (hdr_lbls, cols) = aln(sels, qry_bld.aln)?;
+        Ok(Rfl {
+            hdr_lbls,
+            lblss: sels.iter().map(|x| x.lbls.clone()).collect(),
+            stas: sels.iter().map(|x| x.sta).collect(),
+            mtrs: sels.iter().map(|x| x.mtr).collect(),
+            clds: sels.iter().map(|x| x.cld).collect(),
+            gens: sels.iter().map(|x| x.gen).collect(),
+            tptss: cols
+                .iter()
+                .map(|x| x.iter().map(|y| y.and_then(|z| z.tpt)).collect())
+                .collect(),
+            pek,
+        })
+    }
+    /// Returns percent of peak read bandwidth.
+    pub fn pct_bw(&self, tpt: &Tpt) -> Option<f64> {
+        tpt.byt_s.map(|x| x / self.pek.rd_bw * 100.0)
+    }
+    /// Returns percent of peak SIMD operation rate.
+    pub fn pct_ops(&self, tpt: &Tpt) -> Option<f64> {
+        tpt.ops_s.map(|x| x / self.pek.sim_ops * 100.0)
+    }
+    /// Returns percent of the attainable roof at the benchmark's arithmetic intensity.
+    pub fn pct_roof(&self, tpt: &Tpt) -> Option<f64> {
+        match (tpt.ops_s, tpt.ai) {
+            (Some(ops_s), Some(ai)) => Some(ops_s / self.pek.roof(ai) * 100.0),
+            _ => None,
+        }
+    }
+}
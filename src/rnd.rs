@@ -0,0 +1,107 @@
+//! Provides a seeded pseudo-random number generator.
+
+use crate::*;
+
+/// A seeded pseudo-random number generator.
+///
+/// Implements xoshiro256** seeded with SplitMix64.
+/// Identical seeds produce identical sequences on every host.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    s: [u64; 4],
+}
+impl Rng {
+    /// Returns a new generator from a seed.
+    pub fn new(sed: u64) -> Self {
+        let mut x = sed;
+        let mut s = [0u64; 4];
+        for v in s.iter_mut() {
+            // SplitMix64 expands the seed into the generator state.
+            x = x.wrapping_add(GLD);
+            *v = spl_mix(x);
+        }
+        Rng { s }
+    }
+
+    /// Returns a new generator from a seed mixed with a value.
+    ///
+    /// Mixing is fixed, so streams are identical across hosts and Rust releases.
+    pub fn new_u64(sed: u64, x: u64) -> Self {
+        Rng::new(mix(sed, x))
+    }
+
+    /// Returns a new generator from a seed mixed with a hashable value.
+    ///
+    /// Useful for deriving independent, reproducible streams within a build.
+    /// Hashing uses `DefaultHasher`, which may change across Rust releases;
+    /// use `new_u64` for streams reproduced across releases.
+    pub fn new_mix<H>(sed: u64, h: H) -> Self
+    where
+        H: Hash,
+    {
+        let mut hsr = DefaultHasher::new();
+        sed.hash(&mut hsr);
+        h.hash(&mut hsr);
+        Rng::new(hsr.finish())
+    }
+
+    /// Returns the next pseudo-random `u64`.
+    #[inline]
+    pub fn u64(&mut self) -> u64 {
+        let ret = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.s[1] << 17;
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+        ret
+    }
+
+    /// Returns a pseudo-random value in the range `[0, n)`.
+    ///
+    /// Uses multiply-shift reduction with rejection to avoid bias.
+    #[inline]
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            return 0;
+        }
+        let thr = n.wrapping_neg() % n;
+        loop {
+            let m = (self.u64() as u128) * (n as u128);
+            if (m as u64) >= thr {
+                return (m >> 64) as u64;
+            }
+        }
+    }
+
+    /// Returns a pseudo-random `f64` in the range `[0, 1)`.
+    #[inline]
+    pub fn f64(&mut self) -> f64 {
+        (self.u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Shuffles a slice in place.
+    pub fn shuffle<T>(&mut self, vals: &mut [T]) {
+        for n in (1..vals.len()).rev() {
+            let m = self.below(n as u64 + 1) as usize;
+            vals.swap(n, m);
+        }
+    }
+}
+
+/// Golden ratio increment of SplitMix64.
+const GLD: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Returns a seed mixed with a value by SplitMix64.
+pub fn mix(sed: u64, x: u64) -> u64 {
+    spl_mix(sed ^ x.wrapping_mul(GLD))
+}
+
+/// Returns the SplitMix64 finalizer of a value.
+fn spl_mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,158 @@
+//! Provides resource usage of benchmark threads.
+
+use crate::*;
+use std::fs;
+
+/// Resource usage counters of the calling thread.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct Rus {
+    /// Voluntary context switches.
+    pub vcsw: u64,
+    /// Involuntary context switches.
+    pub ivcsw: u64,
+    /// Minor page faults.
+    pub minflt: u64,
+    /// Major page faults.
+    pub majflt: u64,
+    /// Peak resident set size in bytes of the process, since the last `rst_hwm`.
+    ///
+    /// Read with `rd_hwm`, since `getrusage` reports a high-water mark
+    /// which is never reset.
+    pub hwm: u64,
+    /// User CPU time in microseconds.
+    pub utime: u64,
+    /// System CPU time in microseconds.
+    pub stime: u64,
+}
+impl Rus {
+    /// Returns resource usage of the calling thread from `getrusage(RUSAGE_THREAD)`.
+    pub fn thd() -> Result<Self> {
+        // Safety: `getrusage` writes a zeroed `rusage` owned by the caller.
+        let (ret, ru) = unsafe {
+            let mut ru: libc::rusage = mem::zeroed();
+            let ret = libc::getrusage(libc::RUSAGE_THREAD, &mut ru);
+            (ret, ru)
+        };
+        if ret != 0 {
+            bail!("getrusage failed: {}", std::io::Error::last_os_error());
+        }
+        Ok(Rus {
+            vcsw: ru.ru_nvcsw as u64,
+            ivcsw: ru.ru_nivcsw as u64,
+            minflt: ru.ru_minflt as u64,
+            majflt: ru.ru_majflt as u64,
+            hwm: 0,
+            utime: tv_us(ru.ru_utime),
+            stime: tv_us(ru.ru_stime),
+        })
+    }
+    /// Returns voluntary and involuntary context switches.
+    pub fn csw(&self) -> u64 {
+        self.vcsw + self.ivcsw
+    }
+    /// Returns resource usage since an earlier reading, in `Mtr::RUS` order.
+    pub fn dlt(&self, fst: &Rus) -> RusVals {
+        [
+            self.minflt.saturating_sub(fst.minflt),
+            self.majflt.saturating_sub(fst.majflt),
+            self.hwm.saturating_sub(fst.hwm),
+            self.utime.saturating_sub(fst.utime),
+            self.stime.saturating_sub(fst.stime),
+        ]
+    }
+}
+
+/// Resets the peak resident set size of the process to its current resident set size.
+///
+/// Writes "5" to `/proc/self/clear_refs`. Returns false when unsupported.
+pub fn rst_hwm() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+/// Returns the peak resident set size in bytes of the process,
+/// from `VmHWM` of `/proc/self/status`.
+pub fn rd_hwm() -> Option<u64> {
+    let sts = fs::read_to_string("/proc/self/status").ok()?;
+    let lin = sts.lines().find(|x| x.starts_with("VmHWM:"))?;
+    let kb: u64 = lin["VmHWM:".len()..]
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+/// Resource usage values, in `Mtr::RUS` order.
+pub type RusVals = [u64; Mtr::RUS.len()];
+
+/// Returns microseconds of a `timeval`.
+fn tv_us(tv: libc::timeval) -> u64 {
+    tv.tv_sec as u64 * 1_000_000 + tv.tv_usec as u64
+}
+
+/// Context switch checks of each sample.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Csw {
+    /// Samples aren't checked.
+    #[default]
+    Off,
+    /// Preempted samples are flagged and kept.
+    Flg,
+    /// Preempted samples are discarded and run again.
+    ///
+    /// A sample runs at most `CSW_TRI` times, and the last run is kept.
+    Dsc,
+}
+
+/// Maximum runs of a discarded sample.
+pub const CSW_TRI: u32 = 8;
+
+/// Context switch contamination of a benchmark.
+#[derive(Debug, Clone)]
+pub struct Pmt<L>
+where
+    L: Label,
+{
+    /// Registration labels.
+    pub lbls: Vec<L>,
+    /// Benchmark label.
+    pub lbl: L,
+    /// Count of preempted samples, including discarded samples.
+    pub cnt: u32,
+    /// Count of samples run, including discarded samples.
+    pub smp_cnt: u32,
+    /// Context switch checks of the samples.
+    pub csw: Csw,
+}
+impl<L> Pmt<L>
+where
+    L: Label,
+{
+    /// Returns the ratio of preempted samples to samples run.
+    pub fn rte(&self) -> f64 {
+        self.cnt as f64 / (self.smp_cnt as f64).max(1.0)
+    }
+}
+impl<L> fmt::Display for Pmt<L>
+where
+    L: Label,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "warning: '{},{}' {} of {} samples ({:.1}%) preempted by context switches; {}",
+            join(&self.lbls, ','),
+            self.lbl,
+            self.cnt,
+            self.smp_cnt,
+            self.rte() * 100.0,
+            if self.csw == Csw::Dsc {
+                "discarded"
+            } else {
+                "flagged"
+            }
+        )
+    }
+}
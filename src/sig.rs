@@ -0,0 +1,304 @@
+//! Provides statistical significance tests between benchmark samples.
+
+use crate::*;
+
+/// Default significance level.
+pub const ALP: f64 = 0.05;
+
+/// Significance tests of the difference between two benchmark samples.
+///
+/// Tests are calculated from summarized benchmark values,
+/// which exclude outliers when outlier exclusion is enabled.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Sig {
+    /// Mann–Whitney U statistic of sample "a".
+    pub mwu_u: f64,
+    /// Two-sided p-value of the Mann–Whitney U test.
+    pub mwu_p: f64,
+    /// Cliff's delta effect size in the range `[-1, 1]`.
+    ///
+    /// Positive values indicate "a" values tend to be larger than "b" values.
+    pub cld: f64,
+    /// Welch's t statistic.
+    pub wlc_t: f64,
+    /// Welch–Satterthwaite degrees of freedom.
+    pub wlc_df: f64,
+    /// Two-sided p-value of Welch's t-test.
+    pub wlc_p: f64,
+    /// Hedges' g effect size.
+    ///
+    /// Positive values indicate "a" has a larger mean than "b".
+    pub hdg: f64,
+}
+impl Sig {
+    /// Returns significance tests of two benchmark samples.
+    pub fn new(a: &Smry, b: &Smry) -> Self {
+        if a.len() < 2 || b.len() < 2 {
+            return Sig {
+                mwu_p: 1.0,
+                wlc_p: 1.0,
+                ..Default::default()
+            };
+        }
+        let mut sig = Sig::default();
+        (sig.mwu_u, sig.mwu_p, sig.cld) = mwu(a.kep(), b.kep());
+        (sig.wlc_t, sig.wlc_df, sig.wlc_p, sig.hdg) = welch(a, b);
+        sig
+    }
+
+    /// Returns true when the Mann–Whitney U test rejects equal distributions
+    /// at significance level `alp`.
+    ///
+    /// Welch's t-test is reported alongside, but isn't used to decide significance
+    /// because heavy-tailed cycle counts inflate its variance estimate.
+    #[inline]
+    pub fn is_sig(&self, alp: f64) -> bool {
+        self.mwu_p < alp
+    }
+}
+
+/// Returns the Mann–Whitney U statistic, two-sided p-value, and Cliff's delta
+/// of two sorted samples.
+///
+/// Uses a normal approximation with tie and continuity corrections.
+fn mwu(a: &[u64], b: &[u64]) -> (f64, f64, f64) {
+    let n1 = a.len() as f64;
+    let n2 = b.len() as f64;
+    let n = n1 + n2;
+
+    // Merge sorted samples, and assign average ranks to ties.
+    let (mut i, mut j) = (0usize, 0usize);
+    let mut rnk = 1f64;
+    let mut r1 = 0f64;
+    let mut tie_sum = 0f64;
+    while i < a.len() || j < b.len() {
+        let v = match (a.get(i), b.get(j)) {
+            (Some(x), Some(y)) => *x.min(y),
+            (Some(x), None) => *x,
+            (None, Some(y)) => *y,
+            (None, None) => unreachable!(),
+        };
+        let a_cnt = a[i..].iter().take_while(|x| **x == v).count();
+        let b_cnt = b[j..].iter().take_while(|x| **x == v).count();
+        let t = (a_cnt + b_cnt) as f64;
+        let avg_rnk = rnk + (t - 1.0) / 2.0;
+        r1 += avg_rnk * a_cnt as f64;
+        tie_sum += t * t * t - t;
+        rnk += t;
+        i += a_cnt;
+        j += b_cnt;
+    }
+
+    let u = r1 - n1 * (n1 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let var = n1 * n2 / 12.0 * ((n + 1.0) - tie_sum / (n * (n - 1.0)));
+    let p = if var <= 0.0 {
+        1.0
+    } else {
+        let z = ((u - mean).abs() - 0.5).max(0.0) / var.sqrt();
+        (2.0 * nrm_sf(z)).min(1.0)
+    };
+    let cld = 2.0 * u / (n1 * n2) - 1.0;
+    (u, p, cld)
+}
+
+/// Returns Welch's t statistic, degrees of freedom, two-sided p-value, and Hedges' g.
+fn welch(a: &Smry, b: &Smry) -> (f64, f64, f64, f64) {
+    let n1 = a.len() as f64;
+    let n2 = b.len() as f64;
+    let dif = a.mean - b.mean;
+
+    // Calculate Hedges' g with a pooled standard deviation.
+    let pld = (((n1 - 1.0) * a.var + (n2 - 1.0) * b.var) / (n1 + n2 - 2.0)).sqrt();
+    let hdg = if pld > 0.0 {
+        dif / pld * (1.0 - 3.0 / (4.0 * (n1 + n2) - 9.0))
+    } else {
+        0.0
+    };
+
+    let se1 = a.var / n1;
+    let se2 = b.var / n2;
+    let se = se1 + se2;
+    if se <= 0.0 {
+        // Identical constant samples have no difference.
+        // Different constant samples have a certain difference.
+        let p = if dif == 0.0 { 1.0 } else { 0.0 };
+        return (0.0, n1 + n2 - 2.0, p, hdg);
+    }
+    let t = dif / se.sqrt();
+    let df = se * se / (se1 * se1 / (n1 - 1.0) + se2 * se2 / (n2 - 1.0));
+    let p = inc_bta(df / 2.0, 0.5, df / (df + t * t)).clamp(0.0, 1.0);
+    (t, df, p, hdg)
+}
+
+/// Returns the survival function of the standard normal distribution.
+fn nrm_sf(z: f64) -> f64 {
+    0.5 * erfc(z / std::f64::consts::SQRT_2)
+}
+
+/// Returns the complementary error function.
+///
+/// Chebyshev approximation with a fractional error below 1.2e-7.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let r = t
+        * (-z * z - 1.265_512_23
+            + t * (1.000_023_68
+                + t * (0.374_091_96
+                    + t * (0.096_784_18
+                        + t * (-0.186_288_06
+                            + t * (0.278_868_07
+                                + t * (-1.135_203_98
+                                    + t * (1.488_515_87
+                                        + t * (-0.822_152_23 + t * 0.170_872_77)))))))))
+            .exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+/// Returns the natural logarithm of the gamma function.
+///
+/// Lanczos approximation.
+fn ln_gam(x: f64) -> f64 {
+    const COF: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut ser = 1.000_000_000_190_015;
+    let mut y = x;
+    for c in COF.iter() {
+        y += 1.0;
+        ser += c / y;
+    }
+    -tmp + (2.506_628_274_631_000_5 * ser / x).ln()
+}
+
+/// Returns the regularized incomplete beta function `I_x(a, b)`.
+fn inc_bta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let bt = (ln_gam(a + b) - ln_gam(a) - ln_gam(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        bt * bta_cf(a, b, x) / a
+    } else {
+        1.0 - bt * bta_cf(b, a, 1.0 - x) / b
+    }
+}
+
+/// Evaluates the continued fraction of the incomplete beta function.
+///
+/// Modified Lentz's method.
+fn bta_cf(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITR: u32 = 300;
+    const EPS: f64 = 3.0e-14;
+    const FPMIN: f64 = 1.0e-300;
+    let qab = a + b;
+    let qap = a + 1.0;
+    let qam = a - 1.0;
+    let mut c = 1.0;
+    let mut d = 1.0 - qab * x / qap;
+    if d.abs() < FPMIN {
+        d = FPMIN;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..=MAX_ITR {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < FPMIN {
+            d = FPMIN;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < FPMIN {
+            c = FPMIN;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 + aa * d;
+        if d.abs() < FPMIN {
+            d = FPMIN;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < FPMIN {
+            c = FPMIN;
+        }
+        d = 1.0 / d;
+        let del = d * c;
+        h *= del;
+        if (del - 1.0).abs() < EPS {
+            break;
+        }
+    }
+    h
+}
+
+#[cfg(test)]
+mod tst {
+    use super::*;
+
+    fn near(a: f64, b: f64, tol: f64) -> bool {
+        (a - b).abs() < tol
+    }
+
+    #[test]
+    fn erfc_ref() {
+        assert!(near(erfc(0.0), 1.0, 1e-6));
+        assert!(near(erfc(1.0), 0.157_299_2, 1e-6));
+        assert!(near(erfc(-1.0), 1.842_700_8, 1e-6));
+        assert!(near(erfc(2.0), 0.004_677_7, 1e-6));
+    }
+
+    #[test]
+    fn mwu_ref() {
+        // Separated samples of five values each.
+        // U = 0, z = 12 / sqrt(22.92) ≈ 2.507 with continuity correction.
+        let (u, p, cld) = mwu(&[1, 2, 3, 4, 5], &[6, 7, 8, 9, 10]);
+        assert!(near(u, 0.0, 1e-9));
+        assert!(near(p, 0.012_186, 1e-4), "{}", p);
+        assert!(near(cld, -1.0, 1e-9));
+
+        // Identical samples have no difference.
+        let (u, p, cld) = mwu(&[1, 2, 3], &[1, 2, 3]);
+        assert!(near(u, 4.5, 1e-9));
+        assert!(near(p, 1.0, 1e-9));
+        assert!(near(cld, 0.0, 1e-9));
+    }
+
+    #[test]
+    fn welch_ref() {
+        // Equal variances of 2.5, and a mean difference of -2.
+        // t = -2 with 8 degrees of freedom.
+        let a = Smry::new(vec![1, 2, 3, 4, 5], false);
+        let b = Smry::new(vec![3, 4, 5, 6, 7], false);
+        let (t, df, p, hdg) = welch(&a, &b);
+        assert!(near(t, -2.0, 1e-9));
+        assert!(near(df, 8.0, 1e-9));
+        assert!(near(p, 0.080_516, 1e-4), "{}", p);
+        assert!(near(hdg, -1.142_5, 1e-3), "{}", hdg);
+    }
+
+    #[test]
+    fn sig_is_sig() {
+        let a = Smry::new((100..130).collect(), false);
+        let b = Smry::new((200..230).collect(), false);
+        assert!(Sig::new(&a, &b).is_sig(ALP));
+        assert!(!Sig::new(&a, &a).is_sig(ALP));
+    }
+}
@@ -0,0 +1,425 @@
+//! Provides a statistical summary of raw benchmark results.
+
+use crate::*;
+use std::ops::Range;
+
+/// A statistical summary of raw benchmark values.
+///
+/// Calculated once per benchmark, and shared by every
+/// selection which relies on the benchmark.
+#[derive(Debug, Clone, Default)]
+pub struct Smry {
+    /// Raw benchmark values sorted in ascending order, including outliers.
+    pub srt: Vec<u64>,
+    /// Range of sorted values which are summarized.
+    ///
+    /// Excluded outliers lie outside the range.
+    pub kep: Range<usize>,
+    /// Minimum benchmark value.
+    pub min: u64,
+    /// Maximum benchmark value.
+    pub max: u64,
+    /// Median benchmark value.
+    pub mdn: u64,
+    /// Average benchmark value.
+    pub avg: u64,
+    /// First quartile.
+    pub q1: f64,
+    /// Third quartile.
+    pub q3: f64,
+    /// Arithmetic mean.
+    pub mean: f64,
+    /// Sample variance.
+    pub var: f64,
+    /// Sample standard deviation.
+    pub std: f64,
+    /// Sample skewness.
+    pub skw: f64,
+    /// Sample excess kurtosis.
+    pub krt: f64,
+    /// Outlier analysis of the raw benchmark values.
+    pub otls: Otls,
+    /// Whether outliers were excluded before summarizing.
+    pub exc: bool,
+}
+impl Smry {
+    /// Returns a new summary of raw benchmark values.
+    ///
+    /// Outliers are excluded from the summary when `exc` is true.
+    /// Outlier counts always describe the raw benchmark values.
+    pub fn new(mut vals: Vec<u64>, exc: bool) -> Self {
+        // Sort once to support order statistics.
+        vals.sort_unstable();
+
+        let len = vals.len();
+        let mut smry = Smry::cal(vals, 0..len);
+        smry.otls = Otls::new(&smry);
+        if exc && smry.otls.cnt() != 0 {
+            // Outliers lie at either end of the sorted values.
+            // Summarize the remaining range.
+            let otls = mem::take(&mut smry.otls);
+            let srt = mem::take(&mut smry.srt);
+            let lo = srt.partition_point(|v| (*v as f64) < otls.mdn && otls.cls(*v) != Otl::Non);
+            let hi = lo + srt[lo..].partition_point(|v| otls.cls(*v) == Otl::Non);
+            smry = Smry::cal(srt, lo..hi);
+            smry.otls = otls;
+            smry.exc = true;
+        }
+        smry
+    }
+
+    /// Calculates statistics of a range of sorted values.
+    fn cal(srt: Vec<u64>, kep: Range<usize>) -> Self {
+        if kep.is_empty() {
+            return Smry {
+                srt,
+                kep,
+                ..Default::default()
+            };
+        }
+
+        let vals = &srt[kep.clone()];
+        let len = vals.len();
+        let sum: u64 = vals.iter().sum();
+        let mean = sum as f64 / len as f64;
+
+        // Calculate central moments in a single pass.
+        let (mut m2, mut m3, mut m4) = (0f64, 0f64, 0f64);
+        for v in vals.iter() {
+            let d = *v as f64 - mean;
+            let d2 = d * d;
+            m2 += d2;
+            m3 += d2 * d;
+            m4 += d2 * d2;
+        }
+        let n = len as f64;
+        let var = if len > 1 { m2 / (n - 1.0) } else { 0.0 };
+        let (skw, krt) = if m2 > 0.0 {
+            let pop_var = m2 / n;
+            (
+                (m3 / n) / pop_var.powf(1.5),
+                (m4 / n) / (pop_var * pop_var) - 3.0,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        Smry {
+            min: vals[0],
+            max: vals[len - 1],
+            mdn: vals[len / 2],
+            avg: sum.saturating_div(len as u64),
+            mean,
+            var,
+            std: var.sqrt(),
+            skw,
+            krt,
+            q1: qnt_srt(vals, 0.25),
+            q3: qnt_srt(vals, 0.75),
+            srt,
+            kep,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the summarized values in ascending order.
+    #[inline]
+    pub fn kep(&self) -> &[u64] {
+        &self.srt[self.kep.clone()]
+    }
+
+    /// Returns the number of summarized values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.kep.len()
+    }
+
+    /// Returns true when there are no summarized values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.kep.is_empty()
+    }
+
+    /// Returns a quantile with linear interpolation.
+    ///
+    /// `p` is a probability in the range `[0, 1]`.
+    pub fn qnt(&self, p: f64) -> f64 {
+        qnt_srt(self.kep(), p)
+    }
+
+    /// Returns the interquartile range.
+    #[inline]
+    pub fn iqr(&self) -> f64 {
+        self.q3 - self.q1
+    }
+
+    /// Returns the value of a statistical function.
+    #[inline]
+    pub fn sta(&self, sta: Sta) -> u64 {
+        match sta {
+            Mdn => self.mdn,
+            Avg => self.avg,
+            Min => self.min,
+            Max => self.max,
+        }
+    }
+}
+
+/// Returns a quantile of sorted values with linear interpolation.
+///
+/// `p` is a probability in the range `[0, 1]`.
+pub fn qnt_srt(srt: &[u64], p: f64) -> f64 {
+    match srt.len() {
+        0 => 0.0,
+        1 => srt[0] as f64,
+        len => {
+            let pos = p.clamp(0.0, 1.0) * (len - 1) as f64;
+            let lo = pos.floor() as usize;
+            let hi = pos.ceil() as usize;
+            let frc = pos - lo as f64;
+            srt[lo] as f64 + (srt[hi] as f64 - srt[lo] as f64) * frc
+        }
+    }
+}
+
+/// Ratio of outliers above which a benchmark is marked with a warning.
+pub const OTL_RTO_WRN: f64 = 0.1;
+
+/// An outlier class of a benchmark value.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Otl {
+    /// Not an outlier.
+    #[default]
+    Non,
+    /// A mild outlier.
+    Mld,
+    /// A severe outlier.
+    Svr,
+}
+
+/// Outlier analysis of benchmark values.
+///
+/// Values are classified with Tukey fences and MAD-based z-scores.
+/// A value is an outlier of a class when both methods agree,
+/// which avoids flagging discrete cycle counts with a zero
+/// interquartile range.
+#[derive(Debug, Clone, Default)]
+pub struct Otls {
+    /// Lower and upper Tukey fences for mild outliers (1.5 IQR).
+    pub mld_fnc: (f64, f64),
+    /// Lower and upper Tukey fences for severe outliers (3 IQR).
+    pub svr_fnc: (f64, f64),
+    /// Median of benchmark values.
+    pub mdn: f64,
+    /// Median absolute deviation scaled to be consistent with a standard deviation.
+    pub mad: f64,
+    /// Count of low mild outliers.
+    pub lo_mld: u32,
+    /// Count of low severe outliers.
+    pub lo_svr: u32,
+    /// Count of high mild outliers.
+    pub hi_mld: u32,
+    /// Count of high severe outliers.
+    pub hi_svr: u32,
+    /// Count of analyzed values.
+    pub len: u32,
+}
+impl Otls {
+    /// Z-score above which a value is a mild outlier.
+    pub const MLD_Z: f64 = 3.5;
+    /// Z-score above which a value is a severe outlier.
+    pub const SVR_Z: f64 = 7.0;
+
+    /// Returns an outlier analysis of summarized values.
+    pub fn new(smry: &Smry) -> Self {
+        if smry.is_empty() {
+            return Otls::default();
+        }
+
+        // Calculate Tukey fences.
+        let iqr = smry.iqr();
+        let mut otls = Otls {
+            mld_fnc: (smry.q1 - 1.5 * iqr, smry.q3 + 1.5 * iqr),
+            svr_fnc: (smry.q1 - 3.0 * iqr, smry.q3 + 3.0 * iqr),
+            mdn: smry.qnt(0.5),
+            len: smry.len() as u32,
+            ..Default::default()
+        };
+
+        // Calculate the median absolute deviation.
+        // Fall back to the mean absolute deviation when
+        // more than half the values equal the median.
+        let mut devs: Vec<f64> = smry
+            .kep()
+            .iter()
+            .map(|v| (*v as f64 - otls.mdn).abs())
+            .collect();
+        devs.sort_unstable_by(|a, b| a.total_cmp(b));
+        let mdl = devs.len() / 2;
+        otls.mad = devs[mdl] * 1.4826;
+        if otls.mad == 0.0 {
+            otls.mad = devs.iter().sum::<f64>() / devs.len() as f64 * 1.2533;
+        }
+
+        // Count outliers by class.
+        for v in smry.kep().iter() {
+            let hi = *v as f64 > otls.mdn;
+            match (otls.cls(*v), hi) {
+                (Otl::Non, _) => {}
+                (Otl::Mld, false) => otls.lo_mld += 1,
+                (Otl::Mld, true) => otls.hi_mld += 1,
+                (Otl::Svr, false) => otls.lo_svr += 1,
+                (Otl::Svr, true) => otls.hi_svr += 1,
+            }
+        }
+        otls
+    }
+
+    /// Classifies a benchmark value.
+    pub fn cls(&self, v: u64) -> Otl {
+        if self.mad == 0.0 {
+            return Otl::Non;
+        }
+        let v = v as f64;
+
+        // Classify with Tukey fences.
+        let tky = if v < self.svr_fnc.0 || v > self.svr_fnc.1 {
+            Otl::Svr
+        } else if v < self.mld_fnc.0 || v > self.mld_fnc.1 {
+            Otl::Mld
+        } else {
+            Otl::Non
+        };
+
+        // Classify with a MAD-based z-score.
+        let z = (v - self.mdn).abs() / self.mad;
+        let mad = if z > Self::SVR_Z {
+            Otl::Svr
+        } else if z > Self::MLD_Z {
+            Otl::Mld
+        } else {
+            Otl::Non
+        };
+
+        tky.min(mad)
+    }
+
+    /// Returns the count of mild outliers.
+    #[inline]
+    pub fn mld(&self) -> u32 {
+        self.lo_mld + self.hi_mld
+    }
+
+    /// Returns the count of severe outliers.
+    #[inline]
+    pub fn svr(&self) -> u32 {
+        self.lo_svr + self.hi_svr
+    }
+
+    /// Returns the count of all outliers.
+    #[inline]
+    pub fn cnt(&self) -> u32 {
+        self.mld() + self.svr()
+    }
+
+    /// Returns the ratio of outliers to analyzed values.
+    #[inline]
+    pub fn rto(&self) -> f64 {
+        if self.len == 0 {
+            0.0
+        } else {
+            self.cnt() as f64 / self.len as f64
+        }
+    }
+
+    /// Returns true when the outlier ratio warrants a warning.
+    #[inline]
+    pub fn wrn(&self) -> bool {
+        self.rto() > OTL_RTO_WRN
+    }
+}
+
+#[cfg(test)]
+mod tst {
+    use super::*;
+
+    #[test]
+    fn otl_zero_iqr() {
+        // Discrete cycle counts one cycle from the median aren't outliers,
+        // even though the interquartile range is zero.
+        let mut vals = vec![100u64; 16];
+        vals.extend([99, 99, 101, 101, 500]);
+        let smry = Smry::new(vals, false);
+        assert_eq!(smry.iqr(), 0.0);
+        assert_eq!(smry.otls.cls(99), Otl::Non);
+        assert_eq!(smry.otls.cls(101), Otl::Non);
+        assert_eq!(smry.otls.cls(500), Otl::Svr);
+        assert_eq!((smry.otls.mld(), smry.otls.hi_svr), (0, 1));
+    }
+
+    #[test]
+    fn otl_mad_fallback() {
+        // More than half the values equal the median,
+        // so the mean absolute deviation is scaled instead.
+        let mut vals = vec![100u64; 16];
+        vals.extend([99, 99, 101, 101, 500]);
+        let smry = Smry::new(vals, false);
+        let mad = 404.0 / 21.0 * 1.2533;
+        assert!((smry.otls.mad - mad).abs() < 1e-9, "{}", smry.otls.mad);
+
+        // Identical values have no outliers.
+        let smry = Smry::new(vec![7; 10], false);
+        assert_eq!(smry.otls.mad, 0.0);
+        assert_eq!(smry.otls.cnt(), 0);
+    }
+
+    #[test]
+    fn otl_agree() {
+        // Tukey fences flag a value the MAD z-score doesn't,
+        // so the value isn't an outlier.
+        let otls = Otls {
+            mld_fnc: (90.0, 110.0),
+            svr_fnc: (80.0, 120.0),
+            mdn: 100.0,
+            mad: 10.0,
+            ..Default::default()
+        };
+        assert_eq!(otls.cls(125), Otl::Non);
+        assert_eq!(otls.cls(140), Otl::Mld);
+        assert_eq!(otls.cls(200), Otl::Svr);
+        assert_eq!(otls.cls(0), Otl::Svr);
+    }
+
+    #[test]
+    fn otl_exc_both_tails() {
+        let mut vals: Vec<u64> = (1000..1020).collect();
+        vals.extend([10, 5000]);
+
+        let smry = Smry::new(vals.clone(), false);
+        assert_eq!((smry.otls.lo_svr, smry.otls.hi_svr), (1, 1));
+        assert_eq!(smry.kep, 0..22);
+        assert_eq!((smry.min, smry.max), (10, 5000));
+        assert!(!smry.exc);
+
+        let smry = Smry::new(vals, true);
+        assert_eq!(smry.kep, 1..21);
+        assert_eq!(smry.len(), 20);
+        assert_eq!((smry.min, smry.max), (1000, 1019));
+        assert_eq!(smry.srt.len(), 22);
+        assert_eq!(smry.otls.len, 22);
+        assert!(smry.exc);
+    }
+
+    #[test]
+    fn otl_exc_one_tail() {
+        let mut vals: Vec<u64> = (1000..1020).collect();
+        vals.extend([9000, 9100, 9200]);
+        let smry = Smry::new(vals, true);
+        assert_eq!(smry.kep, 0..20);
+        assert_eq!(smry.max, 1019);
+        assert_eq!(smry.otls.hi_svr, 3);
+        // Three outliers of twenty three values exceed the warning ratio.
+        assert!(smry.otls.wrn());
+    }
+}
@@ -0,0 +1,82 @@
+//! Provides parameter sweeps for benchmark registrations.
+
+use crate::*;
+
+/// A generator of struct label values for a parameter sweep.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Swp {
+    /// A list of values.
+    Lst(Vec<u32>),
+    /// A list of geometrically spaced values, e.g. cache sizes.
+    LogLst(Vec<u32>),
+    /// A linear range from first to last, inclusive, with a step.
+    Lin(u32, u32, u32),
+    /// Powers of two from first to last, inclusive.
+    Pow2(u32, u32),
+    /// A geometric progression from first to last, inclusive, with a ratio.
+    ///
+    /// Values are rounded and deduplicated.
+    Geo(u32, u32, f64),
+}
+impl Swp {
+    /// Returns the sweep values in order.
+    pub fn vals(&self) -> Vec<u32> {
+        match self {
+            Swp::Lst(vals) | Swp::LogLst(vals) => vals.clone(),
+            Swp::Lin(fst, lst, stp) => (*fst..=*lst).step_by((*stp).max(1) as usize).collect(),
+            Swp::Pow2(fst, lst) => {
+                let mut vals = Vec::new();
+                let mut val = fst.max(&1).next_power_of_two() as u64;
+                while val <= *lst as u64 {
+                    vals.push(val as u32);
+                    val *= 2;
+                }
+                vals
+            }
+            Swp::Geo(fst, lst, rto) => {
+                let mut vals: Vec<u32> = Vec::new();
+                let mut val = (*fst).max(1) as f64;
+                while val.round() <= *lst as f64 {
+                    let x = val.round() as u32;
+                    if vals.last() != Some(&x) {
+                        vals.push(x);
+                    }
+                    if *rto <= 1.0 {
+                        break;
+                    }
+                    val *= rto;
+                }
+                vals
+            }
+        }
+    }
+    /// Returns the spacing of sweep values.
+    pub fn spc(&self) -> Spc {
+        match self {
+            Swp::Lst(_) | Swp::Lin(..) => Spc::Lin,
+            Swp::LogLst(_) | Swp::Pow2(..) | Swp::Geo(..) => Spc::Log,
+        }
+    }
+}
+
+/// The spacing of struct label values along a series axis.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub enum Spc {
+    /// Evenly spaced values, suited to a linear axis.
+    #[default]
+    Lin,
+    /// Geometrically spaced values, suited to a log-scale axis.
+    Log,
+}
+impl Spc {
+    /// Returns a value interpolated between `lo` and `hi` at fraction `t`.
+    ///
+    /// Log spacing interpolates geometrically.
+    pub fn itp(&self, lo: f64, hi: f64, t: f64) -> f64 {
+        match self {
+            Spc::Log if lo > 0.0 && hi > 0.0 => (lo.ln() + (hi.ln() - lo.ln()) * t).exp(),
+            _ => lo + (hi - lo) * t,
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Provides command-line table printing.
 
 use crate::*;
-use comfy_table::{presets::UTF8_FULL, Table};
+use comfy_table::{presets::UTF8_FULL, Cell, Color, Table};
 
 impl<L> fmt::Display for Cmp<L>
 where
@@ -12,37 +12,711 @@ where
         tbl.load_preset(UTF8_FULL);
 
         // Write header.
-        let mut hdr: Vec<String> = Vec::with_capacity(1 + self.hdr_lbls.len());
-        hdr.push(format!("{:#}", self.hdr_lbls[0]));
-        for hdr_lbl in self.hdr_lbls.iter() {
-            hdr.push(fmt_num(hdr_lbl.val().unwrap()));
+        // Labels are annotated with the memory level of the working set.
+        let mut hdr = hdr_row(&self.hdr_lbls);
+        for (n, mem) in self.mems.iter().enumerate() {
+            if let Some(mem) = mem {
+                hdr[1 + n] = format!("{}\n{}", hdr[1 + n], mem);
+            }
         }
         tbl.set_header(hdr);
 
         // Write "a" values.
-        let mut a_row: Vec<String> = Vec::with_capacity(1 + self.a_vals.len());
-        a_row.push(join(&self.a_lbls, ','));
-        for a in self.a_vals.iter() {
-            a_row.push(fmt_num(a));
+        // Winners are optionally highlighted.
+        let a_win_cnt = self.win_cnt(Win::A);
+        let b_win_cnt = self.win_cnt(Win::B);
+        let mut a_row: Vec<Cell> = Vec::with_capacity(1 + self.a_vals.len());
+        a_row.push(clr_cell(self.a_nam(), self.clr && a_win_cnt > b_win_cnt));
+        for n in 0..self.a_vals.len() {
+            a_row.push(clr_cell(
+                fmt_val(self.a_vals[n], &self.a_cis[n], &self.a_smrys[n]),
+                self.clr && self.wins[n] == Some(Win::A),
+            ));
         }
         tbl.add_row(a_row);
 
         // Write "b" values.
-        let mut b_row: Vec<String> = Vec::with_capacity(1 + self.b_vals.len());
-        b_row.push(join(&self.b_lbls, ','));
-        for b in self.b_vals.iter() {
-            b_row.push(fmt_num(b));
+        let mut b_row: Vec<Cell> = Vec::with_capacity(1 + self.b_vals.len());
+        b_row.push(clr_cell(self.b_nam(), self.clr && b_win_cnt > a_win_cnt));
+        for n in 0..self.b_vals.len() {
+            b_row.push(clr_cell(
+                fmt_val(self.b_vals[n], &self.b_cis[n], &self.b_smrys[n]),
+                self.clr && self.wins[n] == Some(Win::B),
+            ));
         }
         tbl.add_row(b_row);
 
         // Write ratio values.
         let mut ratio_row: Vec<String> = Vec::with_capacity(1 + self.ratios.len());
         ratio_row.push("ratio (max / min)".into());
-        for ratio in self.ratios.iter() {
-            ratio_row.push(fmt_num(ratio));
+        for n in 0..self.ratios.len() {
+            let Some(ratio) = self.ratios[n] else {
+                ratio_row.push(String::new());
+                continue;
+            };
+            let mut s = match self.ratio_cis[n] {
+                None => fmt_f32(ratio),
+                Some(ci) => format!(
+                    "{}× ({}–{})",
+                    fmt_f32(ratio),
+                    fmt_f32(ci.lo as f32),
+                    fmt_f32(ci.hi as f32)
+                ),
+            };
+            if self.sigs[n].is_some_and(|x| !x.is_sig(self.alp)) {
+                s.push(' ');
+                s.push(NSG_MRK);
+            }
+            ratio_row.push(s);
         }
         tbl.add_row(ratio_row);
 
+        // Write directional speedups.
+        let mut spd_row: Vec<String> = Vec::with_capacity(1 + self.spds.len());
+        spd_row.push("speedup (a / b)".into());
+        for spd in self.spds.iter() {
+            spd_row.push(spd.map_or_else(String::new, |x| format!("{}×", fmt_spd(x))));
+        }
+        tbl.add_row(spd_row);
+
+        // Write percentage changes.
+        let mut pct_row: Vec<String> = Vec::with_capacity(1 + self.pcts.len());
+        pct_row.push("change (b vs a)".into());
+        for pct in self.pcts.iter() {
+            pct_row.push(pct.map_or_else(String::new, fmt_pct));
+        }
+        tbl.add_row(pct_row);
+
+        // Write p-values.
+        let mut p_row: Vec<String> = Vec::with_capacity(1 + self.sigs.len());
+        p_row.push("p (mwu / welch)".into());
+        for sig in self.sigs.iter() {
+            p_row.push(sig.map_or_else(String::new, |x| {
+                format!("{} / {}", fmt_p(x.mwu_p), fmt_p(x.wlc_p))
+            }));
+        }
+        tbl.add_row(p_row);
+
+        // Write effect sizes.
+        let mut eff_row: Vec<String> = Vec::with_capacity(1 + self.sigs.len());
+        eff_row.push("effect (δ / g)".into());
+        for sig in self.sigs.iter() {
+            eff_row.push(sig.map_or_else(String::new, |x| format!("{:.2} / {:.2}", x.cld, x.hdg)));
+        }
+        tbl.add_row(eff_row);
+
+        // Write winners.
+        let mut win_row: Vec<String> = Vec::with_capacity(1 + self.wins.len());
+        win_row.push("winner".into());
+        for win in self.wins.iter() {
+            win_row.push(
+                match win {
+                    None => "",
+                    Some(Win::A) => "a",
+                    Some(Win::B) => "b",
+                    Some(Win::Tie) => "tie",
+                }
+                .into(),
+            );
+        }
+        tbl.add_row(win_row);
+
+        // Write a summary of wins.
+        tbl.add_row(vec![
+            "wins (a / b / tie)".into(),
+            format!("{} / {} / {}", a_win_cnt, b_win_cnt, self.win_cnt(Win::Tie)),
+        ]);
+        tbl.add_row(vec![
+            "geomean speedup".into(),
+            format!("{}×", fmt_spd(self.geo_spd())),
+        ]);
+
+        // Write throughput of each side.
+        for row in tpt_rows(&self.a_nam(), &self.a_tpts) {
+            tbl.add_row(row);
+        }
+        for row in tpt_rows(&self.b_nam(), &self.b_tpts) {
+            tbl.add_row(row);
+        }
+
+        // Write resource usage of each side.
+        if self.rus {
+            for row in rus_rows(&self.a_nam(), &self.a_russ) {
+                tbl.add_row(row);
+            }
+            for row in rus_rows(&self.b_nam(), &self.b_russ) {
+                tbl.add_row(row);
+            }
+        }
+
+        f.write_fmt(format_args!("{}", tbl))?;
+
+        // Write crossovers.
+        // Labels without a numeric value have no crossovers to report.
+        if self.xovrs.is_empty() && self.hdr_lbls.iter().all(|x| x.val().is_ok()) {
+            f.write_fmt(format_args!("\nno crossover"))?;
+        }
+        for xovr in self.xovrs.iter() {
+            let nam = |win: Win| match win {
+                Win::A => self.a_nam(),
+                _ => self.b_nam(),
+            };
+            f.write_fmt(format_args!(
+                "\ncrossover at {:#} ≈ {}: {} faster below, {} faster above",
+                xovr.lo_lbl,
+                fmt_num(xovr.val.round() as u64),
+                nam(xovr.blw),
+                nam(xovr.abv)
+            ))?;
+        }
+
+        // Write a significance note.
+        if self.sigs.iter().flatten().any(|x| !x.is_sig(self.alp)) {
+            f.write_fmt(format_args!(
+                "\n{} not significant at α = {}",
+                NSG_MRK, self.alp
+            ))?;
+        }
+        fmt::Result::Ok(())
+    }
+}
+
+impl<L> fmt::Display for Mlt<L>
+where
+    L: Label,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tbl = Table::new();
+        tbl.load_preset(UTF8_FULL);
+
+        // Write header.
+        tbl.set_header(hdr_row(&self.hdr_lbls));
+
+        // Write values of each selection.
+        // The fastest values are optionally highlighted.
+        for (m, vals) in self.valss.iter().enumerate() {
+            let mut row: Vec<Cell> = Vec::with_capacity(1 + vals.len());
+            row.push(Cell::new(self.nam(m)));
+            for (n, val) in vals.iter().enumerate() {
+                row.push(clr_cell(
+                    fmt_val(*val, &self.ciss[m][n], &self.smryss[m][n]),
+                    self.clr && self.rnkss[m][n] == Some(1),
+                ));
+            }
+            tbl.add_row(row);
+        }
+
+        // Write ratios to the reference selection.
+        let ref_nam = self.nam(self.ref_idx);
+        for (m, ratios) in self.ratioss.iter().enumerate() {
+            if m == self.ref_idx {
+                continue;
+            }
+            let mut row: Vec<String> = Vec::with_capacity(1 + ratios.len());
+            row.push(format!("{} / {}", self.nam(m), ref_nam));
+            for ratio in ratios.iter() {
+                row.push(ratio.map_or_else(String::new, fmt_f32));
+            }
+            tbl.add_row(row);
+        }
+
+        // Write ranks of each selection.
+        for (m, rnks) in self.rnkss.iter().enumerate() {
+            let mut row: Vec<String> = Vec::with_capacity(1 + rnks.len());
+            row.push(format!("rank {}", self.nam(m)));
+            for rnk in rnks.iter() {
+                row.push(rnk.map_or_else(String::new, |x| x.to_string()));
+            }
+            tbl.add_row(row);
+        }
+
+        // Write throughput of each selection.
+        for (m, tpts) in self.tptss.iter().enumerate() {
+            for row in tpt_rows(&self.nam(m), tpts) {
+                tbl.add_row(row);
+            }
+        }
+
+        f.write_fmt(format_args!("{}", tbl))
+    }
+}
+
+impl<L> fmt::Display for Nrm<L>
+where
+    L: Label,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tbl = Table::new();
+        tbl.load_preset(UTF8_FULL);
+
+        // Write header.
+        tbl.set_header(hdr_row(&self.hdr_lbls));
+
+        // Write normalized values of each selection.
+        // The reference selection is the first series.
+        for (m, ser) in self.sers.iter().enumerate() {
+            let mut row: Vec<String> = Vec::with_capacity(1 + ser.vals.len());
+            let mut nam = sel_nam(&ser.lbls, ser.sta, ser.mtr, ser.cld, ser.gen);
+            if m == 0 {
+                nam.push_str(" (ref)");
+            }
+            row.push(nam);
+            for val in ser.vals.iter() {
+                row.push(val.map_or_else(String::new, fmt_f32));
+            }
+            tbl.add_row(row);
+        }
+
         f.write_fmt(format_args!("{}", tbl))
     }
 }
+
+impl<L> fmt::Display for Fit<L>
+where
+    L: Label,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tbl = Table::new();
+        tbl.load_preset(UTF8_FULL);
+
+        // Write header.
+        tbl.set_header(hdr_row(&self.hdr_lbls));
+
+        // Write selection values.
+        let mut row: Vec<String> = Vec::with_capacity(1 + self.vals.len());
+        row.push(sel_nam(&self.lbls, self.sta, self.mtr, self.cld, self.gen));
+        for val in self.vals.iter() {
+            row.push(fmt_num(val));
+        }
+        tbl.add_row(row);
+
+        // Write cycles per unit of n.
+        let mut row: Vec<String> = Vec::with_capacity(1 + self.per_ns.len());
+        row.push(format!("cycles / {:#}", self.hdr_lbls[0]));
+        for per_n in self.per_ns.iter() {
+            row.push(fmt_f32(*per_n as f32));
+        }
+        tbl.add_row(row);
+
+        // Write values of the best fit.
+        let best = self.best();
+        let mut row: Vec<String> = Vec::with_capacity(1 + self.ns.len());
+        row.push(format!("fit {}", best.cpx));
+        for n in self.ns.iter() {
+            row.push(fmt_f32(best.at(*n) as f32));
+        }
+        tbl.add_row(row);
+
+        f.write_fmt(format_args!("{}", tbl))?;
+
+        // Write fits from best to worst.
+        for (m, fit) in self.fits.iter().enumerate() {
+            f.write_fmt(format_args!(
+                "\n{} {}: coef {}, R² {:.3}, rms {}%",
+                if m == 0 { "best" } else { "    " },
+                fit.cpx,
+                fmt_coef(fit.coef),
+                fit.r2,
+                fmt_f32(fit.rms as f32 * 100.0)
+            ))?;
+        }
+        fmt::Result::Ok(())
+    }
+}
+
+/// Returns throughput rows of a selection.
+///
+/// Rows are written only for declared counts.
+fn tpt_rows(nam: &str, tpts: &[Option<Tpt>]) -> Vec<Vec<String>> {
+    type FmtTpt = fn(&Tpt) -> Option<String>;
+    let cols: [(&str, FmtTpt); 4] = [
+        ("cycles / elm", |x| x.cyc_elm.map(fmt_cyc)),
+        ("Melm/s", |x| x.elm_s.map(|y| fmt_f32((y / 1e6) as f32))),
+        ("cycles / B", |x| x.cyc_byt.map(fmt_cyc)),
+        ("GB/s", |x| x.byt_s.map(|y| fmt_f32((y / 1e9) as f32))),
+    ];
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for (unt, f) in cols {
+        if !tpts.iter().flatten().any(|x| f(x).is_some()) {
+            continue;
+        }
+        let mut row: Vec<String> = Vec::with_capacity(1 + tpts.len());
+        row.push(format!("{} {}", nam, unt));
+        for tpt in tpts.iter() {
+            row.push(tpt.as_ref().and_then(f).unwrap_or_default());
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+/// Returns rows of resource usage per sample.
+///
+/// Rows where every value is zero are skipped.
+fn rus_rows(nam: &str, russ: &[Option<RusVals>]) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for (n, mtr) in Mtr::RUS.iter().enumerate() {
+        if !russ.iter().flatten().any(|x| x[n] != 0) {
+            continue;
+        }
+        let mut row: Vec<String> = Vec::with_capacity(1 + russ.len());
+        row.push(format!("{} {}", nam, mtr));
+        for rus in russ.iter() {
+            row.push(rus.map_or_else(String::new, |x| fmt_num(x[n])));
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+/// Returns formatted cycles per unit, with more precision below ten.
+fn fmt_cyc(cyc: f64) -> String {
+    if cyc < 10.0 {
+        format!("{:.2}", cyc)
+    } else {
+        fmt_f32(cyc as f32)
+    }
+}
+
+/// Returns a formatted speedup, with three significant figures below one.
+///
+/// Large slowdowns stay visible, e.g. `0.0213` rather than `0`.
+fn fmt_spd(spd: f32) -> String {
+    if spd > 0.0 && spd < 1.0 {
+        let prc = (2 - spd.log10().floor() as i32).clamp(2, 9) as usize;
+        format!("{:.*}", prc, spd)
+    } else {
+        fmt_f32(spd)
+    }
+}
+
+/// Returns a formatted fit coefficient, in scientific notation when small.
+fn fmt_coef(coef: f64) -> String {
+    if coef == 0.0 || coef.abs() >= 0.01 {
+        format!("{:.4}", coef)
+    } else {
+        format!("{:.3e}", coef)
+    }
+}
+
+/// Returns a header row of struct label values.
+///
+/// The first cell is the struct label name.
+fn hdr_row<L>(hdr_lbls: &[L]) -> Vec<String>
+where
+    L: Label,
+{
+    let mut hdr: Vec<String> = Vec::with_capacity(1 + hdr_lbls.len());
+    hdr.push(format!("{:#}", hdr_lbls[0]));
+    for hdr_lbl in hdr_lbls.iter() {
+        hdr.push(hdr_lbl.hdr());
+    }
+    hdr
+}
+
+/// Returns the display name of a selection.
+///
+/// Statistical functions other than the default median, metrics
+/// other than the default cycles, cold cache states, and input
+/// generators are appended, which distinguishes selections with
+/// identical labels and reproduces inputs.
+fn sel_nam<L>(lbls: &Vec<L>, sta: Sta, mtr: Mtr, cld: Cld, gen: SelGen) -> String
+where
+    L: Label,
+{
+    let mut s = join(lbls, ',');
+    if sta != Sta::default() {
+        s.push_str(&format!(" ({:?})", sta).to_lowercase());
+    }
+    if mtr != Mtr::default() {
+        s.push_str(&format!(" ({})", mtr));
+    }
+    if !cld.is_wrm() {
+        s.push_str(&format!(" ({})", cld));
+    }
+    if gen != SelGen::Non {
+        s.push_str(&format!(" ({})", gen));
+    }
+    s
+}
+
+impl<L> Cmp<L>
+where
+    L: Label,
+{
+    /// Returns the display name of "a".
+    fn a_nam(&self) -> String {
+        sel_nam(&self.a_lbls, self.a_sta, self.a_mtr, self.a_cld, self.a_gen)
+    }
+    /// Returns the display name of "b".
+    fn b_nam(&self) -> String {
+        sel_nam(&self.b_lbls, self.b_sta, self.b_mtr, self.b_cld, self.b_gen)
+    }
+}
+
+impl<L> Mlt<L>
+where
+    L: Label,
+{
+    /// Returns the display name of a selection.
+    fn nam(&self, idx: usize) -> String {
+        sel_nam(
+            &self.lblss[idx],
+            self.stas[idx],
+            self.mtrs[idx],
+            self.clds[idx],
+            self.gens[idx],
+        )
+    }
+}
+
+impl<L> fmt::Display for Rfl<L>
+where
+    L: Label,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tbl = Table::new();
+        tbl.load_preset(UTF8_FULL);
+
+        // Write header.
+        tbl.set_header(hdr_row(&self.hdr_lbls));
+
+        // Write intensity and percentages of peak for each selection.
+        type FmtRfl<L> = fn(&Rfl<L>, &Tpt) -> Option<String>;
+        let cols: [(&str, FmtRfl<L>); 4] = [
+            ("op / B", |_, x| x.ai.map(fmt_cyc)),
+            ("% peak bw", |r, x| r.pct_bw(x).map(|y| fmt_f32(y as f32))),
+            ("% peak ops", |r, x| r.pct_ops(x).map(|y| fmt_f32(y as f32))),
+            ("% roof", |r, x| r.pct_roof(x).map(|y| fmt_f32(y as f32))),
+        ];
+        for (m, tpts) in self.tptss.iter().enumerate() {
+            for (unt, g) in cols.iter() {
+                if !tpts.iter().flatten().any(|x| g(self, x).is_some()) {
+                    continue;
+                }
+                let mut row: Vec<String> = Vec::with_capacity(1 + tpts.len());
+                row.push(format!("{} {}", self.nam(m), unt));
+                for tpt in tpts.iter() {
+                    row.push(tpt.as_ref().and_then(|x| g(self, x)).unwrap_or_default());
+                }
+                tbl.add_row(row);
+            }
+        }
+
+        f.write_fmt(format_args!("{}", tbl))?;
+
+        // Write machine peaks.
+        f.write_fmt(format_args!(
+            "\npeak: read {}B/s, write {}B/s, scalar {}op/s, {} simd {}op/s",
+            fmt_si(self.pek.rd_bw),
+            fmt_si(self.pek.wr_bw),
+            fmt_si(self.pek.sca_ops),
+            self.pek.sim,
+            fmt_si(self.pek.sim_ops)
+        ))?;
+
+        self.fmt_cht(f)
+    }
+}
+
+impl<L> Rfl<L>
+where
+    L: Label,
+{
+    /// Returns the display name of a selection.
+    fn nam(&self, idx: usize) -> String {
+        sel_nam(
+            &self.lblss[idx],
+            self.stas[idx],
+            self.mtrs[idx],
+            self.clds[idx],
+            self.gens[idx],
+        )
+    }
+
+    /// Writes a log-log roofline chart of operations per second by arithmetic intensity.
+    ///
+    /// Each selection is plotted with a letter at every label.
+    fn fmt_cht(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHT_W: usize = 60;
+        const CHT_H: usize = 15;
+
+        // Collect points with declared bytes and operations.
+        let mut pts: Vec<(usize, f64, f64)> = Vec::new();
+        for (m, tpts) in self.tptss.iter().enumerate() {
+            for tpt in tpts.iter().flatten() {
+                if let (Some(ai), Some(ops_s)) = (tpt.ai, tpt.ops_s) {
+                    pts.push((m, ai, ops_s));
+                }
+            }
+        }
+        if pts.is_empty() {
+            return f.write_fmt(format_args!(
+                "\nno roofline chart: no benchmarks declare bytes and operations"
+            ));
+        }
+
+        // Set log axis ranges around the points and the ridge point.
+        let rdg = self.pek.rdg();
+        let ai_lo = pts.iter().map(|x| x.1).fold(rdg, f64::min) / 4.0;
+        let ai_hi = pts.iter().map(|x| x.1).fold(rdg, f64::max) * 4.0;
+        let ops_lo = pts
+            .iter()
+            .map(|x| x.2)
+            .fold(self.pek.roof_sca(ai_lo), f64::min)
+            / 2.0;
+        let ops_hi = pts.iter().map(|x| x.2).fold(self.pek.sim_ops, f64::max) * 2.0;
+        let col = |ai: f64| {
+            let x = (ai.ln() - ai_lo.ln()) / (ai_hi.ln() - ai_lo.ln());
+            ((x * (CHT_W - 1) as f64).round() as usize).min(CHT_W - 1)
+        };
+        let row = |ops: f64| {
+            let y = (ops.ln() - ops_lo.ln()) / (ops_hi.ln() - ops_lo.ln());
+            CHT_H - 1 - ((y * (CHT_H - 1) as f64).round().max(0.0) as usize).min(CHT_H - 1)
+        };
+
+        // Draw scalar, write and read SIMD roofs.
+        // Roofs meet at the compute peak, where the read roof is drawn.
+        let mut grd = vec![vec![' '; CHT_W]; CHT_H];
+        let ais = (0..CHT_W).map(|c| {
+            (ai_lo.ln() + (ai_hi.ln() - ai_lo.ln()) * c as f64 / (CHT_W - 1) as f64).exp()
+        });
+        for (c, ai) in ais.enumerate() {
+            grd[row(self.pek.roof_sca(ai))][c] = '·';
+            grd[row(self.pek.roof_wr(ai))][c] = ':';
+            grd[row(self.pek.roof(ai))][c] = if ai < rdg { '/' } else { '─' };
+        }
+
+        // Draw points.
+        for (m, ai, ops_s) in pts {
+            grd[row(ops_s)][col(ai)] = (b'a' + (m % 26) as u8) as char;
+        }
+
+        // Write chart rows with axis labels.
+        f.write_fmt(format_args!("\nop/s"))?;
+        for (r, cs) in grd.iter().enumerate() {
+            let lbl = match r {
+                0 => fmt_si(ops_hi),
+                _ if r == CHT_H - 1 => fmt_si(ops_lo),
+                _ => String::new(),
+            };
+            let s: String = cs.iter().collect();
+            f.write_fmt(format_args!("\n{:>6} │{}", lbl, s.trim_end()))?;
+        }
+        f.write_fmt(format_args!("\n{:>6} └{}", "", "─".repeat(CHT_W)))?;
+        let ai_lo = fmt_cyc(ai_lo);
+        let ai_hi = fmt_cyc(ai_hi);
+        f.write_fmt(format_args!(
+            "\n{:>8}{}{}{} op / B",
+            "",
+            ai_lo,
+            " ".repeat(CHT_W.saturating_sub(ai_lo.len() + ai_hi.len())),
+            ai_hi
+        ))?;
+
+        // Write a legend.
+        f.write_fmt(format_args!(
+            "\n/ read roof, : write roof, ─ {} simd roof, · scalar roof",
+            self.pek.sim
+        ))?;
+        for m in 0..self.lblss.len() {
+            f.write_fmt(format_args!(
+                "\n{} {}",
+                (b'a' + (m % 26) as u8) as char,
+                self.nam(m)
+            ))?;
+        }
+        fmt::Result::Ok(())
+    }
+}
+
+/// Returns a formatted value with an SI prefix, e.g. `12 G`.
+fn fmt_si(v: f64) -> String {
+    let (div, pfx) = match v.abs() {
+        x if x >= 1e12 => (1e12, "T"),
+        x if x >= 1e9 => (1e9, "G"),
+        x if x >= 1e6 => (1e6, "M"),
+        x if x >= 1e3 => (1e3, "k"),
+        _ => (1.0, ""),
+    };
+    format!("{} {}", fmt_f32((v / div) as f32), pfx)
+}
+
+/// Writes an outlier warning for a benchmark metric with a high outlier rate.
+impl<L> fmt::Display for OtlWrn<L>
+where
+    L: Label,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}", OTL_MRK, join(&self.lbls, ','), self.lbl)?;
+        if self.mtr != Mtr::Cyc {
+            write!(f, " ({})", self.mtr)?;
+        }
+        let otls = &self.smry.otls;
+        write!(
+            f,
+            ": {} outliers of {} ({} mild, {} severe{})",
+            otls.cnt(),
+            otls.len,
+            otls.mld(),
+            otls.svr(),
+            if self.smry.exc { ", excluded" } else { "" }
+        )
+    }
+}
+
+/// Marks a value with a high outlier rate.
+const OTL_MRK: char = '!';
+
+/// Marks a difference which isn't statistically significant.
+const NSG_MRK: char = '~';
+
+/// Returns a table cell, highlighted when `win` is true.
+fn clr_cell(s: String, win: bool) -> Cell {
+    if win {
+        Cell::new(s).fg(Color::Green)
+    } else {
+        Cell::new(s)
+    }
+}
+
+/// Formats a signed percentage.
+fn fmt_pct(v: f32) -> String {
+    if v > 0.0 {
+        format!("+{}%", fmt_f32(v))
+    } else {
+        format!("{}%", fmt_f32(v))
+    }
+}
+
+/// Formats a p-value.
+fn fmt_p(p: f64) -> String {
+    if p < 0.001 {
+        "<0.001".into()
+    } else {
+        format!("{:.3}", p)
+    }
+}
+
+/// Formats a statistical value with an optional confidence interval,
+/// and an outlier warning marker when necessary.
+///
+/// Missing values are blank.
+fn fmt_val(v: Option<u64>, ci: &Option<Ci>, smry: &Option<Arc<Smry>>) -> String {
+    let (Some(v), Some(smry)) = (v, smry) else {
+        return String::new();
+    };
+    let mut s = fmt_num(v);
+    if let Some(ci) = ci {
+        s.push_str(&format!(
+            " ({}–{})",
+            fmt_num(ci.lo.round() as u64),
+            fmt_num(ci.hi.round() as u64)
+        ));
+    }
+    if smry.otls.wrn() {
+        s.push(' ');
+        s.push(OTL_MRK);
+    }
+    s
+}
@@ -0,0 +1,168 @@
+//! Provides throughput declarations and measured throughput.
+
+use crate::*;
+use std::time::{Duration, Instant};
+
+/// A count of units processed by one benchmark call.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Cnt {
+    /// A fixed count.
+    Fix(u64),
+    /// A count derived from the benchmark's struct label value,
+    /// multiplied by the specified factor.
+    ///
+    /// For example, `Len(n)` with `Cnt::Lbl(4)` processes `4n` units.
+    Lbl(u64),
+    /// A count derived from the benchmark's element count,
+    /// multiplied by the specified factor.
+    ///
+    /// For example, bytes of `n` elements of `u32` are `Cnt::Elm(4)`.
+    Elm(u64),
+}
+impl Cnt {
+    /// Returns the count for a benchmark label and element count.
+    ///
+    /// Returns `None` for a label count when the label has no numeric value, e.g. `Ty(Typ)`.
+    pub fn cal<L>(&self, lbl: L, elm: Option<u64>) -> Result<Option<u64>>
+    where
+        L: Label,
+    {
+        match self {
+            Cnt::Fix(cnt) => Ok(Some(*cnt)),
+            Cnt::Lbl(mul) => match lbl.val() {
+                Err(_) => Ok(None),
+                Result::Ok(val) => Cnt::mul(lbl, val as u64, *mul).map(Some),
+            },
+            Cnt::Elm(mul) => match elm {
+                None => bail!(
+                    "missing element count: benchmark '{}' declares a count per element",
+                    lbl
+                ),
+                Some(elm) => Cnt::mul(lbl, elm, *mul).map(Some),
+            },
+        }
+    }
+
+    /// Returns a count multiplied by a factor, or an error on overflow.
+    fn mul<L>(lbl: L, cnt: u64, mul: u64) -> Result<u64>
+    where
+        L: Label,
+    {
+        match cnt.checked_mul(mul) {
+            None => bail!(
+                "count overflow: benchmark '{}' count {} times factor {} exceeds u64",
+                lbl,
+                cnt,
+                mul
+            ),
+            Some(x) => Ok(x),
+        }
+    }
+}
+
+/// Throughput declared for a benchmark.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct Thr {
+    /// Elements processed per call.
+    pub elm: Option<Cnt>,
+    /// Bytes processed per call.
+    pub byt: Option<Cnt>,
+    /// Arithmetic operations performed per call.
+    pub ops: Option<Cnt>,
+}
+impl Thr {
+    /// Returns whether no throughput is declared.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.elm.is_none() && self.byt.is_none() && self.ops.is_none()
+    }
+}
+
+/// Counts processed by one benchmark call, resolved from a throughput declaration.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub struct Cnts {
+    pub elm: Option<u64>,
+    pub byt: Option<u64>,
+    pub ops: Option<u64>,
+}
+impl Thr {
+    /// Returns counts for a benchmark label.
+    pub fn cnts<L>(&self, lbl: L) -> Result<Cnts>
+    where
+        L: Label,
+    {
+        let elm = self.elm.map(|x| x.cal(lbl, None)).transpose()?.flatten();
+        // Counts per element are skipped with a skipped element count.
+        let cal = |cnt: Option<Cnt>| match cnt {
+            Some(Cnt::Elm(_)) if self.elm.is_some() && elm.is_none() => Ok(None),
+            Some(cnt) => cnt.cal(lbl, elm),
+            None => Ok(None),
+        };
+        Ok(Cnts {
+            elm,
+            byt: cal(self.byt)?,
+            ops: cal(self.ops)?,
+        })
+    }
+}
+
+/// Throughput measured for a statistical value.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Tpt {
+    /// Cycles per element.
+    pub cyc_elm: Option<f64>,
+    /// Cycles per byte.
+    pub cyc_byt: Option<f64>,
+    /// Elements per second.
+    pub elm_s: Option<f64>,
+    /// Bytes per second.
+    pub byt_s: Option<f64>,
+    /// Operations per second.
+    pub ops_s: Option<f64>,
+    /// Arithmetic intensity in operations per byte.
+    pub ai: Option<f64>,
+}
+impl Tpt {
+    /// Returns throughput of a cycle value, converted to time with a TSC rate.
+    ///
+    /// Returns `None` when no counts are declared.
+    pub fn new(val: u64, cnts: Cnts, hz: f64) -> Option<Self> {
+        let Cnts { elm, byt, ops } = cnts;
+        if elm.is_none() && byt.is_none() && ops.is_none() {
+            return None;
+        }
+        let cyc = (val as f64).max(1.0);
+        let sec = cyc / hz;
+        Some(Tpt {
+            cyc_elm: elm.map(|x| cyc / (x as f64).max(1.0)),
+            cyc_byt: byt.map(|x| cyc / (x as f64).max(1.0)),
+            elm_s: elm.map(|x| x as f64 / sec),
+            byt_s: byt.map(|x| x as f64 / sec),
+            ops_s: ops.map(|x| x as f64 / sec),
+            ai: match (ops, byt) {
+                (Some(ops), Some(byt)) => Some(ops as f64 / (byt as f64).max(1.0)),
+                _ => None,
+            },
+        })
+    }
+}
+
+/// Returns the measured timestamp counter rate in cycles per second.
+///
+/// Counts timestamp cycles while spinning on the monotonic clock.
+pub fn tsc_hz() -> f64 {
+    let dur = Duration::from_millis(20);
+    // Take the median of three runs to reduce scheduling noise.
+    let mut hzs = [0f64; 3];
+    for hz in hzs.iter_mut() {
+        let fst_ins = Instant::now();
+        let fst = fst_cpu_cyc();
+        while fst_ins.elapsed() < dur {}
+        let lst = lst_cpu_cyc();
+        let sec = fst_ins.elapsed().as_secs_f64();
+        *hz = (lst - fst) as f64 / sec;
+    }
+    hzs.sort_by(|a, b| a.total_cmp(b));
+    hzs[1]
+}
@@ -0,0 +1,57 @@
+//! Provides generic-type sweeps for benchmark registrations.
+
+use crate::*;
+use std::any::type_name;
+
+/// A type used as a benchmark label value, e.g. `Ty(Typ)`.
+///
+/// Types order by size, then by name.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct Typ {
+    /// Size of the type in bytes.
+    pub siz: u32,
+    /// Name of the type without its module path.
+    pub nam: &'static str,
+}
+impl Typ {
+    /// Returns the label value of a type.
+    pub fn of<T>() -> Self {
+        let nam = type_name::<T>();
+        Typ {
+            siz: mem::size_of::<T>() as u32,
+            nam: nam.rsplit("::").next().unwrap_or(nam),
+        }
+    }
+}
+impl fmt::Display for Typ {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.nam)
+    }
+}
+
+/// Inserts one generic benchmark function for each of a list of types.
+///
+/// The type parameter is named for the benchmark body, and each type
+/// becomes a label value through `Typ::of`. Bytes per call are derived
+/// from declared elements and the type size, unless declared explicitly.
+///
+/// ```ignore
+/// stdy.reg_bld(&[Srt], |x| {
+///     x.elm(Cnt::Fix(1024));
+///     ins_typs!(x, Ty, T in [u8, u16, u32, u64, f64], || {
+///         let mut v = vec![T::default(); 1024];
+///         v.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+///         v
+///     });
+/// });
+/// ```
+#[macro_export]
+macro_rules! ins_typs {
+    ($reg_bld:expr, $lbl:path, $t:ident in [$($typ:ty),+ $(,)?], $f:expr) => {{
+        $({
+            #[allow(dead_code)]
+            type $t = $typ;
+            $reg_bld.ins_typ::<$typ, _, _>($lbl($crate::Typ::of::<$typ>()), $f);
+        })+
+    }};
+}